@@ -0,0 +1,160 @@
+use crate::data::tooltip::SegmentKey;
+use crate::utils::comment::*;
+use crate::utils::common::COMMENT_FILE_NAME;
+use eframe::egui;
+
+pub enum CommentAction {
+    Select(SegmentKey),
+    Status(String),
+}
+
+pub struct CommentWindow {
+    pub open: bool,
+    key: Option<SegmentKey>,
+    author: String,
+    draft: String,
+    unresolved_only: bool,
+}
+
+impl Default for CommentWindow {
+    fn default() -> Self {
+        // 預設以系統使用者名稱作為留言者
+        let author = std::env::var("USERNAME")
+            .or_else(|_| std::env::var("USER"))
+            .unwrap_or_default();
+        Self {
+            open: false,
+            key: None,
+            author,
+            draft: String::new(),
+            unresolved_only: true,
+        }
+    }
+}
+
+impl CommentWindow {
+    pub fn open_for(&mut self, key: SegmentKey) {
+        if self.key.as_ref() != Some(&key) {
+            self.draft.clear();
+        }
+        self.key = Some(key);
+        self.open = true;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, comments: &mut Comments) -> Option<CommentAction> {
+        let mut open = self.open;
+        let mut action = None;
+
+        egui::Window::new("留言")
+            .open(&mut open)
+            .default_size([480.0, 480.0])
+            .show(ctx, |ui| {
+                action = self.render_content(ui, comments);
+            });
+
+        self.open = open;
+        action
+    }
+
+    fn render_content(
+        &mut self,
+        ui: &mut egui::Ui,
+        comments: &mut Comments,
+    ) -> Option<CommentAction> {
+        let mut action = None;
+
+        ui.horizontal(|ui| {
+            ui.label("留言者");
+            ui.text_edit_singleline(&mut self.author);
+        });
+
+        if let Some(key) = self.key.clone() {
+            ui.separator();
+            action = self.render_thread(ui, comments, &key);
+        }
+
+        ui.separator();
+        ui.checkbox(&mut self.unresolved_only, "只顯示未解決");
+        egui::ScrollArea::vertical()
+            .id_salt("comment_keys")
+            .show(ui, |ui| {
+                for key in comments.keys(self.unresolved_only) {
+                    let count = comments.thread(&key).len();
+                    let label = format!("{} ({})", key.to_label(), count);
+                    if ui
+                        .selectable_label(self.key.as_ref() == Some(&key), label)
+                        .clicked()
+                    {
+                        self.open_for(key.clone());
+                        action = Some(CommentAction::Select(key));
+                    }
+                }
+            });
+
+        action
+    }
+
+    fn render_thread(
+        &mut self,
+        ui: &mut egui::Ui,
+        comments: &mut Comments,
+        key: &SegmentKey,
+    ) -> Option<CommentAction> {
+        let mut changed = false;
+
+        ui.strong(key.to_label());
+        for comment in comments.thread(key) {
+            let header = format!(
+                "{}  {}",
+                comment.author,
+                format_timestamp(comment.timestamp)
+            );
+            if comment.resolved {
+                ui.weak(header);
+                ui.weak(&comment.text);
+            } else {
+                ui.label(egui::RichText::new(header).strong());
+                ui.label(&comment.text);
+            }
+        }
+
+        ui.add(
+            egui::TextEdit::multiline(&mut self.draft)
+                .desired_rows(3)
+                .desired_width(f32::INFINITY)
+                .hint_text("輸入留言"),
+        );
+        ui.horizontal(|ui| {
+            let can_add = !self.draft.trim().is_empty() && !self.author.trim().is_empty();
+            if ui
+                .add_enabled(can_add, egui::Button::new("新增留言"))
+                .clicked()
+            {
+                comments.add(key, self.author.trim(), self.draft.trim(), now_timestamp());
+                self.draft.clear();
+                changed = true;
+            }
+
+            if ui
+                .add_enabled(
+                    comments.is_unresolved(key),
+                    egui::Button::new("標記為已解決"),
+                )
+                .clicked()
+            {
+                comments.resolve(key);
+                changed = true;
+            }
+        });
+
+        if !changed {
+            return None;
+        }
+        Some(CommentAction::Status(
+            match save_comments(COMMENT_FILE_NAME, comments) {
+                Ok(_) => format!("已更新{}的留言", key.to_label()),
+                Err(e) => format!("留言存檔失敗: {}", e),
+            },
+        ))
+    }
+}
@@ -0,0 +1,92 @@
+use crate::utils::common::GLOSSARY_FILE_NAME;
+use crate::utils::glossary::*;
+use eframe::egui;
+
+#[derive(Default)]
+pub struct GlossaryWindow {
+    pub open: bool,
+}
+
+impl GlossaryWindow {
+    pub fn show(&mut self, ctx: &egui::Context, glossary: &mut Glossary) -> Option<String> {
+        let mut open = self.open;
+        let mut status = None;
+
+        egui::Window::new("術語表")
+            .open(&mut open)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                status = render_content(ui, glossary);
+            });
+
+        self.open = open;
+        status
+    }
+}
+
+fn render_content(ui: &mut egui::Ui, glossary: &mut Glossary) -> Option<String> {
+    let mut status = None;
+
+    ui.horizontal(|ui| {
+        if ui.button("新增術語").clicked() {
+            glossary.entries.push(GlossaryEntry::default());
+        }
+
+        if ui.button("存檔").clicked() {
+            status = Some(match save_glossary(GLOSSARY_FILE_NAME, glossary) {
+                Ok(_) => "術語表存檔成功".to_string(),
+                Err(e) => format!("術語表存檔失敗: {}", e),
+            });
+        }
+
+        if ui.button("重新讀取").clicked() {
+            status = Some(match load_glossary(GLOSSARY_FILE_NAME) {
+                Ok(loaded) => {
+                    *glossary = loaded;
+                    "已重新讀取術語表".to_string()
+                }
+                Err(e) => format!("讀取術語表失敗: {}", e),
+            });
+        }
+    });
+
+    ui.separator();
+
+    let mut removed = None;
+    egui::ScrollArea::vertical()
+        .id_salt("glossary_entries")
+        .show(ui, |ui| {
+            egui::Grid::new("glossary_grid")
+                .num_columns(5)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("原文術語");
+                    ui.label("指定翻譯");
+                    ui.label("備註");
+                    ui.label("禁止譯法(以逗號分隔)");
+                    ui.label("");
+                    ui.end_row();
+
+                    for (index, entry) in glossary.entries.iter_mut().enumerate() {
+                        ui.text_edit_singleline(&mut entry.term);
+                        ui.text_edit_singleline(&mut entry.target);
+                        ui.text_edit_singleline(&mut entry.notes);
+                        // 不在編輯時去除空白，避免輸入逗號時被吃掉
+                        let mut forbidden = entry.forbidden.join(",");
+                        if ui.text_edit_singleline(&mut forbidden).changed() {
+                            entry.forbidden = forbidden.split(',').map(String::from).collect();
+                        }
+                        if ui.button("刪除").clicked() {
+                            removed = Some(index);
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if let Some(index) = removed {
+        glossary.entries.remove(index);
+    }
+
+    status
+}
@@ -0,0 +1,96 @@
+use crate::utils::history::{History, HistoryScope};
+use eframe::egui;
+
+pub enum HistoryAction {
+    Undo,
+    Redo,
+}
+
+#[derive(Default)]
+pub struct HistoryWindow {
+    pub open: bool,
+}
+
+impl HistoryWindow {
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        history: &mut History,
+        current_id: &str,
+    ) -> Option<HistoryAction> {
+        let mut open = self.open;
+        let mut action = None;
+
+        egui::Window::new("編輯記錄")
+            .open(&mut open)
+            .default_size([360.0, 480.0])
+            .show(ctx, |ui| {
+                action = render_content(ui, history, current_id);
+            });
+
+        self.open = open;
+        action
+    }
+}
+
+fn render_content(
+    ui: &mut egui::Ui,
+    history: &mut History,
+    current_id: &str,
+) -> Option<HistoryAction> {
+    let mut action = None;
+
+    ui.horizontal(|ui| {
+        let can_undo = !history.undo_entries(current_id).is_empty();
+        let can_redo = !history.redo_entries(current_id).is_empty();
+        if ui
+            .add_enabled(can_undo, egui::Button::new("復原 (Ctrl+Z)"))
+            .clicked()
+        {
+            action = Some(HistoryAction::Undo);
+        }
+        if ui
+            .add_enabled(can_redo, egui::Button::new("重做 (Ctrl+Y)"))
+            .clicked()
+        {
+            action = Some(HistoryAction::Redo);
+        }
+
+        let mut per_object = history.scope() == HistoryScope::PerObject;
+        if ui
+            .checkbox(&mut per_object, "每個物件分開記錄")
+            .on_hover_text("切換時會清除目前的記錄，批次操作在任何物件都可以復原")
+            .changed()
+        {
+            history.set_scope(match per_object {
+                true => HistoryScope::PerObject,
+                false => HistoryScope::Global,
+            });
+        }
+    });
+
+    ui.separator();
+
+    egui::ScrollArea::vertical()
+        .id_salt("history_entries")
+        .show(ui, |ui| {
+            // 重做的記錄顯示在上方，越接近目前狀態的越下面
+            for command in history.redo_entries(current_id).iter().rev() {
+                ui.weak(format!(
+                    "{} ({}個物件)",
+                    command.label,
+                    command.changes.len()
+                ));
+            }
+            ui.label("── 目前狀態 ──");
+            for command in history.undo_entries(current_id) {
+                ui.label(format!(
+                    "{} ({}個物件)",
+                    command.label,
+                    command.changes.len()
+                ));
+            }
+        });
+
+    action
+}
@@ -0,0 +1,110 @@
+use crate::data::tooltip::SkillManager;
+use crate::utils::history::Snapshot;
+use crate::utils::import::*;
+use eframe::egui;
+
+pub struct ImportWindow {
+    pub open: bool,
+    source_path: String,
+    translation_path: String,
+    candidates: Vec<ImportCandidate>,
+}
+
+impl Default for ImportWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            source_path: REFERENCE_SOURCE_FILE_NAME.to_string(),
+            translation_path: REFERENCE_TRANSLATE_FILE_NAME.to_string(),
+            candidates: vec![],
+        }
+    }
+}
+
+impl ImportWindow {
+    pub fn show(&mut self, ctx: &egui::Context, manager: &mut SkillManager) -> Option<String> {
+        let mut open = self.open;
+        let mut status = None;
+
+        egui::Window::new("匯入參考翻譯")
+            .open(&mut open)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                status = self.render_content(ui, manager);
+            });
+
+        self.open = open;
+        status
+    }
+
+    fn render_content(&mut self, ui: &mut egui::Ui, manager: &mut SkillManager) -> Option<String> {
+        let mut status = None;
+
+        egui::Grid::new("import_paths")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("參考原文");
+                ui.text_edit_singleline(&mut self.source_path);
+                ui.end_row();
+                ui.label("參考翻譯");
+                ui.text_edit_singleline(&mut self.translation_path);
+                ui.end_row();
+            });
+
+        ui.horizontal(|ui| {
+            if ui.button("比對").clicked() {
+                match ReferenceData::load(&self.source_path, &self.translation_path) {
+                    Ok(reference) => {
+                        self.candidates = find_candidates(manager, &reference);
+                        status = Some(format!("找到{}筆可套用的翻譯", self.candidates.len()));
+                    }
+                    Err(e) => status = Some(format!("讀取參考檔案失敗: {}", e)),
+                }
+            }
+
+            if ui.button("全選").clicked() {
+                self.candidates.iter_mut().for_each(|c| c.selected = true);
+            }
+
+            if ui.button("全不選").clicked() {
+                self.candidates.iter_mut().for_each(|c| c.selected = false);
+            }
+
+            if ui.button("套用").clicked() {
+                let snapshot = Snapshot::take_all(manager);
+                let count = apply_candidates(manager, &self.candidates);
+                snapshot.record(manager, "匯入參考翻譯");
+                self.candidates.clear();
+                status = Some(format!("已套用{}筆參考翻譯", count));
+            }
+        });
+
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .id_salt("import_candidates")
+            .show(ui, |ui| {
+                for candidate in self.candidates.iter_mut() {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut candidate.selected, "");
+                        ui.label(format!(
+                            "[{}] {} ← [{}] ({})",
+                            candidate.target_id,
+                            candidate.field_type.to_str(),
+                            candidate.reference_id,
+                            candidate.match_kind.to_str()
+                        ));
+                        if candidate.source_changed {
+                            ui.colored_label(ui.visuals().warn_fg_color, "原文已變更");
+                        }
+                    });
+                    if let Some(first) = candidate.value.first() {
+                        ui.label(first);
+                    }
+                    ui.separator();
+                }
+            });
+
+        status
+    }
+}
@@ -0,0 +1,146 @@
+use crate::data::tooltip::{SegmentKey, SkillManager};
+use crate::ui::diff::diff_layout;
+use crate::utils::diff::diff_tokens;
+use crate::utils::history::Snapshot;
+use crate::utils::import::*;
+use crate::utils::memory::*;
+use eframe::egui;
+
+pub struct MemoryPanel {
+    pub open: bool,
+    source_path: String,
+    translation_path: String,
+    imported: TranslationMemory,
+    cache_key: Option<(SegmentKey, String)>,
+    suggestions: Vec<Suggestion>,
+}
+
+impl Default for MemoryPanel {
+    fn default() -> Self {
+        Self {
+            open: false,
+            source_path: REFERENCE_SOURCE_FILE_NAME.to_string(),
+            translation_path: REFERENCE_TRANSLATE_FILE_NAME.to_string(),
+            imported: TranslationMemory::default(),
+            cache_key: None,
+            suggestions: vec![],
+        }
+    }
+}
+
+impl MemoryPanel {
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        manager: &mut SkillManager,
+        active: Option<&SegmentKey>,
+    ) -> Option<String> {
+        if !self.open {
+            return None;
+        }
+
+        let mut status = None;
+        egui::SidePanel::right("memory_panel")
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                ui.heading("翻譯記憶");
+                ui.separator();
+                status = self.render_import(ui);
+                ui.separator();
+                match active {
+                    Some(key) => self.render_suggestions(ui, manager, key),
+                    None => {
+                        ui.label("請先點選要編輯的翻譯欄位");
+                    }
+                }
+            });
+        status
+    }
+
+    fn render_import(&mut self, ui: &mut egui::Ui) -> Option<String> {
+        let mut status = None;
+        egui::CollapsingHeader::new(format!("匯入翻譯記憶({}筆)", self.imported.entries.len()))
+            .show(ui, |ui| {
+                ui.text_edit_singleline(&mut self.source_path);
+                ui.text_edit_singleline(&mut self.translation_path);
+                if ui.button("匯入").clicked() {
+                    match ReferenceData::load(&self.source_path, &self.translation_path) {
+                        Ok(reference) => {
+                            self.imported = TranslationMemory::from_skills(
+                                &reference.skills,
+                                &reference.translation_skills,
+                                true,
+                            );
+                            self.cache_key = None;
+                            status =
+                                Some(format!("已匯入{}筆翻譯記憶", self.imported.entries.len()));
+                        }
+                        Err(e) => status = Some(format!("匯入翻譯記憶失敗: {}", e)),
+                    }
+                }
+            });
+        status
+    }
+
+    fn render_suggestions(
+        &mut self,
+        ui: &mut egui::Ui,
+        manager: &mut SkillManager,
+        key: &SegmentKey,
+    ) {
+        let Some(source) = manager.get_source(key).cloned() else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(key.to_label());
+            if ui.button("重新整理").clicked() {
+                self.cache_key = None;
+            }
+        });
+
+        let cache_key = Some((key.clone(), source.clone()));
+        if self.cache_key != cache_key {
+            let project =
+                TranslationMemory::from_skills(&manager.skills, &manager.translation_skills, false);
+            let mut suggestions = project.suggest(&source, Some(key), MEMORY_SUGGESTION_LIMIT);
+            suggestions.extend(
+                self.imported
+                    .suggest(&source, None, MEMORY_SUGGESTION_LIMIT),
+            );
+            suggestions.sort_by(|a, b| b.score.total_cmp(&a.score));
+            suggestions.truncate(MEMORY_SUGGESTION_LIMIT);
+            self.suggestions = suggestions;
+            self.cache_key = cache_key;
+        }
+
+        if self.suggestions.is_empty() {
+            ui.label("沒有相似的翻譯");
+            return;
+        }
+
+        egui::ScrollArea::vertical()
+            .id_salt("memory_suggestions")
+            .show(ui, |ui| {
+                for suggestion in &self.suggestions {
+                    ui.group(|ui| {
+                        ui.horizontal(|ui| {
+                            ui.strong(format!("{:.0}%", suggestion.score * 100.0));
+                            ui.label(suggestion.entry.origin.to_label());
+                        });
+                        let ops = diff_tokens(&suggestion.entry.source, &source);
+                        ui.label(diff_layout(ui, &ops));
+                        ui.separator();
+                        ui.label(&suggestion.adapted);
+                        if ui.button("插入").clicked() {
+                            let snapshot = Snapshot::take(manager, [key.id.as_str()]);
+                            if let Some(target) = manager.get_translation_mut(key) {
+                                *target = suggestion.adapted.clone();
+                            }
+                            snapshot.record(manager, format!("插入翻譯記憶到{}", key.to_label()));
+                        }
+                    });
+                }
+            });
+    }
+}
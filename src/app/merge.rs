@@ -0,0 +1,158 @@
+use crate::data::tooltip::SkillManager;
+use crate::utils::history::Snapshot;
+use crate::utils::merge::*;
+use crate::utils::parser::parse_file;
+use crate::utils::status::replace_translations;
+use eframe::egui;
+
+pub struct MergeWindow {
+    pub open: bool,
+    base_path: String,
+    edit_paths: Vec<String>,
+    result: Option<MergeResult>,
+}
+
+impl Default for MergeWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            base_path: MERGE_BASE_FILE_NAME.to_string(),
+            edit_paths: vec![String::new(), String::new()],
+            result: None,
+        }
+    }
+}
+
+impl MergeWindow {
+    pub fn show(&mut self, ctx: &egui::Context, manager: &mut SkillManager) -> Option<String> {
+        let mut open = self.open;
+        let mut status = None;
+
+        egui::Window::new("合併翻譯檔")
+            .open(&mut open)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                status = self.render_content(ui, manager);
+            });
+
+        self.open = open;
+        status
+    }
+
+    fn render_content(&mut self, ui: &mut egui::Ui, manager: &mut SkillManager) -> Option<String> {
+        let mut status = None;
+
+        let mut removed = None;
+        egui::Grid::new("merge_paths")
+            .num_columns(3)
+            .show(ui, |ui| {
+                ui.label("基準翻譯");
+                ui.text_edit_singleline(&mut self.base_path);
+                ui.end_row();
+                let removable = self.edit_paths.len() > 2;
+                for (i, path) in self.edit_paths.iter_mut().enumerate() {
+                    ui.label(format!("翻譯檔案{}", i + 1));
+                    ui.text_edit_singleline(path);
+                    if removable && ui.small_button("移除").clicked() {
+                        removed = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(i) = removed {
+            self.edit_paths.remove(i);
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("新增翻譯檔案").clicked() {
+                self.edit_paths.push(String::new());
+            }
+
+            if ui.button("比對").clicked() {
+                status = Some(match self.load() {
+                    Ok(result) => {
+                        let message = format!(
+                            "自動合併{}個段落，{}個衝突",
+                            result.auto_merged,
+                            result.conflicts.len()
+                        );
+                        self.result = Some(result);
+                        message
+                    }
+                    Err(e) => format!("讀取翻譯檔案失敗: {}", e),
+                });
+            }
+
+            let Some(result) = &self.result else {
+                return;
+            };
+            if ui.button("套用合併結果").clicked() {
+                status = Some(match result.merged_skills() {
+                    Ok(merged) => {
+                        let snapshot = Snapshot::take_all(manager);
+                        replace_translations(manager, merged);
+                        snapshot.record(manager, "合併翻譯檔");
+                        self.result = None;
+                        "已套用合併結果，請記得存檔翻譯".to_string()
+                    }
+                    Err(e) => format!("無法合併: {}", e),
+                });
+            }
+        });
+
+        let Some(result) = &mut self.result else {
+            return status;
+        };
+
+        ui.separator();
+        ui.label(format!(
+            "自動合併{}個段落，{}個衝突中還有{}個尚未解決",
+            result.auto_merged,
+            result.conflicts.len(),
+            result.unresolved()
+        ));
+
+        egui::ScrollArea::vertical()
+            .id_salt("merge_conflicts")
+            .show(ui, |ui| {
+                for conflict in result.conflicts.iter_mut() {
+                    render_conflict(ui, conflict);
+                    ui.separator();
+                }
+            });
+
+        status
+    }
+
+    fn load(&self) -> Result<MergeResult, String> {
+        let base = parse_file(&self.base_path)?;
+        let edits = self
+            .edit_paths
+            .iter()
+            .filter(|path| !path.is_empty())
+            .map(|path| Ok((path.clone(), parse_file(path)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+        if edits.len() < 2 {
+            return Err("至少需要兩個翻譯檔案".to_string());
+        }
+        Ok(merge_translations(&base, &edits))
+    }
+}
+
+// 各版本並排顯示，點選要採用的版本
+fn render_conflict(ui: &mut egui::Ui, conflict: &mut MergeConflict) {
+    ui.strong(conflict.key.to_label());
+    let width = ui.available_width() / conflict.choices.len() as f32 - 8.0;
+    ui.horizontal_top(|ui| {
+        for (i, choice) in conflict.choices.iter().enumerate() {
+            ui.vertical(|ui| {
+                ui.set_width(width);
+                ui.radio_value(&mut conflict.selected, Some(i), &choice.label);
+                match &choice.value {
+                    Some(value) => ui.label(value),
+                    None => ui.weak("(已刪除)"),
+                };
+            });
+        }
+    });
+}
@@ -1 +1,13 @@
-pub mod tooltip;
\ No newline at end of file
+pub mod glossary;
+pub mod history;
+pub mod import;
+pub mod memory;
+pub mod qa;
+pub mod recovery;
+pub mod remap;
+pub mod replace;
+pub mod tooltip;
+pub mod update;
+pub mod merge;
+pub mod orphan;
+pub mod comment;
\ No newline at end of file
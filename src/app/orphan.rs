@@ -0,0 +1,140 @@
+use crate::data::tooltip::SkillManager;
+use crate::utils::archive::archive_translations;
+use crate::utils::comment::{save_comments, Comments};
+use crate::utils::common::{ARCHIVE_FILE_NAME, COMMENT_FILE_NAME};
+use crate::utils::fingerprint::SourceFingerprints;
+use crate::utils::history::Snapshot;
+use crate::utils::orphan::*;
+use eframe::egui;
+
+#[derive(Default)]
+pub struct OrphanWindow {
+    pub open: bool,
+    orphans: Vec<Orphan>,
+}
+
+impl OrphanWindow {
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        manager: &mut SkillManager,
+        fingerprints: &mut SourceFingerprints,
+        comments: &mut Comments,
+    ) -> Option<String> {
+        let mut open = self.open;
+        let mut status = None;
+
+        egui::Window::new("孤立翻譯")
+            .open(&mut open)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                status = self.render_content(ui, manager, fingerprints, comments);
+            });
+
+        self.open = open;
+        status
+    }
+
+    pub fn refresh(&mut self, manager: &SkillManager) {
+        self.orphans = find_orphans(manager);
+    }
+
+    fn render_content(
+        &mut self,
+        ui: &mut egui::Ui,
+        manager: &mut SkillManager,
+        fingerprints: &mut SourceFingerprints,
+        comments: &mut Comments,
+    ) -> Option<String> {
+        let mut status = None;
+
+        ui.label("翻譯中存在但原文已經沒有的物件或欄位，匯出時不會被使用。");
+        ui.horizontal(|ui| {
+            if ui.button("重新整理").clicked() {
+                self.refresh(manager);
+                status = Some(format!("找到{}筆孤立翻譯", self.orphans.len()));
+            }
+
+            if ui.button("全選").clicked() {
+                self.orphans.iter_mut().for_each(|o| o.selected = true);
+            }
+
+            if ui.button("全不選").clicked() {
+                self.orphans.iter_mut().for_each(|o| o.selected = false);
+            }
+
+            if ui
+                .button("封存")
+                .on_hover_text(format!("移到{}", ARCHIVE_FILE_NAME))
+                .clicked()
+            {
+                // 封存檔寫入成功後才從翻譯中移除
+                let selected = selected_orphans(manager, &self.orphans);
+                status = Some(match archive_translations(ARCHIVE_FILE_NAME, selected) {
+                    Ok(count) => {
+                        let snapshot = Snapshot::take_all(manager);
+                        take_orphans(manager, &self.orphans);
+                        snapshot.record(manager, "封存孤立翻譯");
+                        format!("已封存{}個物件的孤立翻譯", count)
+                    }
+                    Err(e) => format!("寫入封存檔失敗，翻譯沒有變更: {}", e),
+                });
+                self.refresh(manager);
+            }
+
+            if ui.button("刪除").clicked() {
+                let snapshot = Snapshot::take_all(manager);
+                let taken = take_orphans(manager, &self.orphans);
+                snapshot.record(manager, "刪除孤立翻譯");
+                status = Some(format!("已刪除{}個物件的孤立翻譯", taken.len()));
+                self.refresh(manager);
+            }
+        });
+
+        ui.separator();
+
+        let mut remapped = None;
+        egui::ScrollArea::vertical()
+            .id_salt("orphans")
+            .show(ui, |ui| {
+                for (i, orphan) in self.orphans.iter_mut().enumerate() {
+                    let label = orphan.to_label();
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut orphan.selected, label);
+                        ui.label("→");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut orphan.target_id)
+                                .desired_width(60.0)
+                                .hint_text("新ID"),
+                        );
+                        if ui.small_button("重新對應").clicked() {
+                            remapped = Some(i);
+                        }
+                    });
+                    ui.weak(&orphan.preview);
+                    ui.separator();
+                }
+            });
+
+        if let Some(orphan) = remapped.and_then(|i| self.orphans.get(i).cloned()) {
+            let snapshot = Snapshot::take(manager, [orphan.id.as_str(), orphan.target_id.trim()]);
+            status = Some(
+                match remap_orphan(manager, fingerprints, comments, &orphan) {
+                    Ok(count) => {
+                        let label =
+                            format!("{}對應到[{}]", orphan.to_label(), orphan.target_id.trim());
+                        snapshot.record(manager, label.clone());
+                        match save_comments(COMMENT_FILE_NAME, comments) {
+                            Ok(_) => format!("已將{}，共{}個欄位", label, count),
+                            Err(e) => format!("已將{}，但留言存檔失敗: {}", label, e),
+                        }
+                    }
+                    Err(e) => format!("無法重新對應: {}", e),
+                },
+            );
+            self.refresh(manager);
+        }
+
+        status
+    }
+}
@@ -0,0 +1,277 @@
+use crate::data::tooltip::{SegmentKey, TooltipData};
+use crate::utils::common::{QA_EXCEPTION_FILE_NAME, QA_SETTINGS_FILE_NAME};
+use crate::utils::history::Snapshot;
+use crate::utils::qa::check::*;
+use crate::utils::qa::exception::*;
+use crate::utils::qa::settings::*;
+use crate::utils::script::Script;
+use eframe::egui;
+
+pub enum QaAction {
+    Select(SegmentKey),
+    Status(String),
+}
+
+#[derive(Default)]
+pub struct QaWindow {
+    pub open: bool,
+    issues: Vec<QaIssue>,
+    new_language: String,
+}
+
+impl QaWindow {
+    pub fn issues(&self) -> &[QaIssue] {
+        &self.issues
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, data: &mut TooltipData) -> Option<QaAction> {
+        let mut open = self.open;
+        let mut action = None;
+
+        egui::Window::new("QA檢查")
+            .open(&mut open)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                action = self.render_content(ui, data);
+            });
+
+        self.open = open;
+        action
+    }
+
+    fn render_content(&mut self, ui: &mut egui::Ui, data: &mut TooltipData) -> Option<QaAction> {
+        let mut action = None;
+
+        ui.horizontal(|ui| {
+            if ui.button("執行檢查").clicked() {
+                let context = QaContext {
+                    glossary: &data.glossary,
+                    exceptions: &data.qa_exceptions,
+                    settings: &data.qa_settings,
+                };
+                self.issues = run_checks(&data.skill_manager, &context);
+            }
+            ui.label(format!("共{}個問題", self.issues.len()));
+        });
+
+        if let Some(status) = self.render_settings(ui, &mut data.qa_settings) {
+            action = Some(QaAction::Status(status));
+        }
+        if let Some(status) = render_exceptions(ui, &mut data.qa_exceptions) {
+            action = Some(QaAction::Status(status));
+        }
+
+        ui.separator();
+
+        let mut excepted = None;
+        let mut fixed = None;
+        egui::ScrollArea::vertical()
+            .id_salt("qa_issues")
+            .show(ui, |ui| {
+                egui::Grid::new("qa_grid")
+                    .num_columns(6)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (index, issue) in self.issues.iter().enumerate() {
+                            let color = match issue.severity {
+                                Severity::Error => ui.visuals().error_fg_color,
+                                Severity::Warning => ui.visuals().warn_fg_color,
+                                Severity::Info => ui.visuals().text_color(),
+                            };
+                            ui.colored_label(color, issue.severity.to_str());
+                            ui.label(issue.rule.to_str());
+                            if ui.link(issue.key.to_label()).clicked() {
+                                action = Some(QaAction::Select(issue.key.clone()));
+                            }
+                            let translation = data.skill_manager.get_translation(&issue.key);
+                            match translation.and_then(|t| issue.position(t)) {
+                                Some(position) => ui.label(format!("第{}字", position)),
+                                None => ui.label(""),
+                            };
+                            ui.label(&issue.message);
+                            ui.horizontal(|ui| {
+                                if issue.rule.is_fixable() && ui.button("修正").clicked() {
+                                    fixed = Some(index);
+                                }
+                                if ui
+                                    .button("標記例外")
+                                    .on_hover_text("刻意保留，之後的檢查不再提出")
+                                    .clicked()
+                                {
+                                    excepted = Some(index);
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if let Some(index) = fixed {
+            let issue = self.issues[index].clone();
+            let manager = &mut data.skill_manager;
+            let snapshot = Snapshot::take(manager, [issue.key.id.as_str()]);
+            let fixed = apply_fix(manager, &issue);
+            let label = format!("修正{}的{}問題", issue.key.to_label(), issue.rule.to_str());
+            snapshot.record(manager, label);
+            if fixed {
+                self.issues
+                    .retain(|i| i.key != issue.key || i.rule != issue.rule);
+                action = Some(QaAction::Status(format!(
+                    "已修正{}的{}問題",
+                    issue.key.to_label(),
+                    issue.rule.to_str()
+                )));
+            }
+        }
+
+        if let Some(index) = excepted {
+            let issue = self.issues.remove(index);
+            data.qa_exceptions.insert(&issue);
+            action = Some(QaAction::Status(save_status(&data.qa_exceptions)));
+        }
+
+        action
+    }
+
+    fn render_settings(&mut self, ui: &mut egui::Ui, settings: &mut QaSettings) -> Option<String> {
+        let mut status = None;
+        egui::CollapsingHeader::new("設定")
+            .id_salt("qa_settings")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("目標語言");
+                    egui::ComboBox::from_id_salt("qa_language")
+                        .selected_text(settings.language.as_str())
+                        .show_ui(ui, |ui| {
+                            for profile in &settings.script_profiles {
+                                let language = profile.language.clone();
+                                ui.selectable_value(
+                                    &mut settings.language,
+                                    language.clone(),
+                                    language,
+                                );
+                            }
+                        });
+                    ui.text_edit_singleline(&mut self.new_language);
+                    let language = self.new_language.trim().to_string();
+                    let exists = settings
+                        .script_profiles
+                        .iter()
+                        .any(|p| p.language == language);
+                    if ui
+                        .add_enabled(
+                            !language.is_empty() && !exists,
+                            egui::Button::new("新增語言"),
+                        )
+                        .clicked()
+                    {
+                        settings.script_profiles.push(ScriptProfile {
+                            language: language.clone(),
+                            expected: vec![],
+                            forbidden: vec![],
+                        });
+                        settings.language = language;
+                        self.new_language.clear();
+                    }
+                });
+
+                if let Some(profile) = settings
+                    .script_profiles
+                    .iter_mut()
+                    .find(|p| p.language == settings.language)
+                {
+                    egui::Grid::new("qa_script_grid")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label("文字");
+                            ui.label("應該使用");
+                            ui.label("不應出現");
+                            ui.end_row();
+                            for script in Script::ALL {
+                                ui.label(script.to_label());
+                                toggle_script(ui, &mut profile.expected, script);
+                                toggle_script(ui, &mut profile.forbidden, script);
+                                ui.end_row();
+                            }
+                        });
+                }
+
+                ui.separator();
+                ui.label("長度上限(0表示不限制)");
+                egui::Grid::new("qa_budget_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("欄位");
+                        ui.label("行數");
+                        ui.label("每行寬度");
+                        ui.end_row();
+                        for budget in settings.budgets.iter_mut() {
+                            ui.label(budget.field_type.to_str());
+                            ui.add(egui::DragValue::new(&mut budget.max_lines));
+                            ui.add(egui::DragValue::new(&mut budget.max_width));
+                            ui.end_row();
+                        }
+                    });
+
+                if ui.button("儲存設定").clicked() {
+                    status = Some(match save_settings(QA_SETTINGS_FILE_NAME, settings) {
+                        Ok(_) => "QA設定已儲存".to_string(),
+                        Err(e) => format!("QA設定存檔失敗: {}", e),
+                    });
+                }
+            });
+        status
+    }
+}
+
+fn toggle_script(ui: &mut egui::Ui, scripts: &mut Vec<Script>, script: Script) {
+    let mut checked = scripts.contains(&script);
+    if ui.checkbox(&mut checked, "").changed() {
+        if checked {
+            scripts.push(script);
+        } else {
+            scripts.retain(|s| *s != script);
+        }
+    }
+}
+
+fn render_exceptions(ui: &mut egui::Ui, exceptions: &mut QaExceptions) -> Option<String> {
+    let mut removed = None;
+    egui::CollapsingHeader::new(format!("例外清單({})", exceptions.entries.len()))
+        .id_salt("qa_exceptions")
+        .show(ui, |ui| {
+            egui::ScrollArea::vertical()
+                .id_salt("qa_exception_list")
+                .max_height(160.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("qa_exception_grid")
+                        .num_columns(4)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (index, exception) in exceptions.entries.iter().enumerate() {
+                                ui.label(exception.key.to_label());
+                                let rule = QaRule::from_id(&exception.rule);
+                                ui.label(rule.map_or(exception.rule.as_str(), |r| r.to_str()));
+                                ui.label(&exception.message);
+                                if ui.button("移除").clicked() {
+                                    removed = Some(index);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+
+    let index = removed?;
+    exceptions.entries.remove(index);
+    Some(save_status(exceptions))
+}
+
+fn save_status(exceptions: &QaExceptions) -> String {
+    match save_exceptions(QA_EXCEPTION_FILE_NAME, exceptions) {
+        Ok(_) => "QA例外清單已儲存".to_string(),
+        Err(e) => format!("QA例外清單存檔失敗: {}", e),
+    }
+}
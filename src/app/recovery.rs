@@ -0,0 +1,111 @@
+use crate::data::tooltip::SkillManager;
+use crate::ui::diff::diff_layout;
+use crate::utils::diff::diff_tokens;
+use crate::utils::history::Snapshot;
+use crate::utils::recovery::*;
+use eframe::egui;
+
+#[derive(Default)]
+pub struct RecoveryWindow {
+    pub open: bool,
+    journal: Option<Journal>,
+    changes: Vec<RecoveredChange>,
+}
+
+impl RecoveryWindow {
+    // 啟動時讀取上次留下的復原檔，內容與存檔相同時直接刪除
+    pub fn load(manager: &SkillManager) -> Self {
+        let Some(journal) = load_journal() else {
+            return Self::default();
+        };
+        let changes = recovered_changes(manager, &journal);
+        if changes.is_empty() {
+            let _ = remove_journal();
+            return Self::default();
+        }
+        Self {
+            open: true,
+            journal: Some(journal),
+            changes,
+        }
+    }
+
+    // 使用者決定前不能覆寫復原檔
+    pub fn is_pending(&self) -> bool {
+        self.journal.is_some()
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, manager: &mut SkillManager) -> Option<String> {
+        if !self.open {
+            return None;
+        }
+        let mut status = None;
+
+        egui::Window::new("復原未存檔的翻譯")
+            .collapsible(false)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                status = self.render_content(ui, manager);
+            });
+
+        status
+    }
+
+    fn render_content(&mut self, ui: &mut egui::Ui, manager: &mut SkillManager) -> Option<String> {
+        let mut status = None;
+
+        ui.label(format!(
+            "上次關閉前有{}個段落的翻譯或狀態尚未存檔，是否要還原？",
+            self.changes.len()
+        ));
+        ui.horizontal(|ui| {
+            if ui.button("還原").clicked() {
+                if let Some(journal) = self.journal.take() {
+                    let snapshot = Snapshot::take_all(manager);
+                    journal.restore(manager);
+                    snapshot.record(manager, "還原未存檔的翻譯");
+                    status = Some(format!("已還原{}個段落的翻譯", self.changes.len()));
+                }
+                self.close();
+            }
+
+            if ui.button("捨棄").clicked() {
+                status = Some(match remove_journal() {
+                    Ok(_) => "已捨棄未存檔的翻譯".to_string(),
+                    Err(e) => format!("刪除復原檔失敗: {}", e),
+                });
+                self.journal = None;
+                self.close();
+            }
+        });
+
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .id_salt("recovery_changes")
+            .show(ui, |ui| {
+                for change in &self.changes {
+                    ui.strong(change.key.to_label());
+                    let saved = change.saved.as_deref().unwrap_or_default();
+                    let recovered = change.recovered.as_deref().unwrap_or_default();
+                    let ops = diff_tokens(saved, recovered);
+                    ui.label(diff_layout(ui, &ops));
+                    if change.saved_status != change.recovered_status {
+                        ui.label(format!(
+                            "狀態: {} → {}",
+                            change.saved_status.to_str(),
+                            change.recovered_status.to_str()
+                        ));
+                    }
+                    ui.separator();
+                }
+            });
+
+        status
+    }
+
+    fn close(&mut self) {
+        self.open = false;
+        self.changes.clear();
+    }
+}
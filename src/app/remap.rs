@@ -0,0 +1,128 @@
+use crate::data::tooltip::SkillManager;
+use crate::utils::common::TRANSLATE_FILE_NAME;
+use crate::utils::history::Snapshot;
+use crate::utils::import::ReferenceData;
+use crate::utils::remap::*;
+use eframe::egui;
+
+pub struct RemapWindow {
+    pub open: bool,
+    source_path: String,
+    translation_path: String,
+    legacy: Option<ReferenceData>,
+    proposals: Vec<RemapProposal>,
+}
+
+impl Default for RemapWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            source_path: LEGACY_SOURCE_FILE_NAME.to_string(),
+            translation_path: TRANSLATE_FILE_NAME.to_string(),
+            legacy: None,
+            proposals: vec![],
+        }
+    }
+}
+
+impl RemapWindow {
+    pub fn show(&mut self, ctx: &egui::Context, manager: &mut SkillManager) -> Option<String> {
+        let mut open = self.open;
+        let mut status = None;
+
+        egui::Window::new("技能ID重新對應")
+            .open(&mut open)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                status = self.render_content(ui, manager);
+            });
+
+        self.open = open;
+        status
+    }
+
+    fn render_content(&mut self, ui: &mut egui::Ui, manager: &mut SkillManager) -> Option<String> {
+        let mut status = None;
+
+        egui::Grid::new("remap_paths")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("舊版原文");
+                ui.text_edit_singleline(&mut self.source_path);
+                ui.end_row();
+                ui.label("舊版翻譯");
+                ui.text_edit_singleline(&mut self.translation_path);
+                ui.end_row();
+            });
+
+        ui.horizontal(|ui| {
+            if ui.button("建議配對").clicked() {
+                match ReferenceData::load(&self.source_path, &self.translation_path) {
+                    Ok(legacy) => {
+                        self.proposals = propose_remaps(manager, &legacy);
+                        self.legacy = Some(legacy);
+                        status = Some(format!("找到{}組可能的ID對應", self.proposals.len()));
+                    }
+                    Err(e) => status = Some(format!("讀取舊版檔案失敗: {}", e)),
+                }
+            }
+
+            if ui.button("套用").clicked() {
+                if let Some(legacy) = self.legacy.take() {
+                    let snapshot = Snapshot::take_all(manager);
+                    let count = apply_remaps(manager, &legacy, &self.proposals);
+                    snapshot.record(manager, "ID重新對應");
+                    self.proposals.clear();
+                    status = Some(format!("已搬移{}個技能的翻譯", count));
+                }
+            }
+        });
+
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .id_salt("remap_proposals")
+            .show(ui, |ui| {
+                egui::Grid::new("remap_grid")
+                    .num_columns(6)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("");
+                        ui.label("舊ID → 新ID");
+                        ui.label("相似度");
+                        ui.label("名稱");
+                        ui.label("父技能/熱鍵");
+                        ui.label("說明文字");
+                        ui.end_row();
+
+                        for proposal in self.proposals.iter_mut() {
+                            ui.checkbox(&mut proposal.selected, "");
+                            ui.label(format!("{} → {}", proposal.old_id, proposal.new_id));
+                            ui.label(format_percent(Some(proposal.score)));
+                            ui.label(format_percent(proposal.name_score));
+                            ui.label(format!(
+                                "{} / {}",
+                                format_match(proposal.parent_match),
+                                format_match(proposal.hotkey_match)
+                            ));
+                            ui.label(format_percent(Some(proposal.text_score)));
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        status
+    }
+}
+
+fn format_percent(score: Option<f32>) -> String {
+    score.map_or("-".to_string(), |s| format!("{:.0}%", s * 100.0))
+}
+
+fn format_match(matched: Option<bool>) -> &'static str {
+    match matched {
+        Some(true) => "相同",
+        Some(false) => "不同",
+        None => "-",
+    }
+}
@@ -0,0 +1,144 @@
+use crate::data::tooltip::SkillManager;
+use crate::ui::diff::diff_layout;
+use crate::utils::common::{FieldType, ObjectTable};
+use crate::utils::diff::diff_tokens;
+use crate::utils::history::Snapshot;
+use crate::utils::replace::*;
+use eframe::egui;
+
+#[derive(Default)]
+pub struct ReplaceWindow {
+    pub open: bool,
+    query: ReplaceQuery,
+    matches: Vec<ReplaceMatch>,
+}
+
+impl ReplaceWindow {
+    pub fn show(&mut self, ctx: &egui::Context, manager: &mut SkillManager) -> Option<String> {
+        let mut open = self.open;
+        let mut status = None;
+
+        egui::Window::new("尋找與取代")
+            .open(&mut open)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                status = self.render_content(ui, manager);
+            });
+
+        self.open = open;
+        status
+    }
+
+    fn render_content(&mut self, ui: &mut egui::Ui, manager: &mut SkillManager) -> Option<String> {
+        let mut status = None;
+
+        self.render_query(ui);
+
+        ui.horizontal(|ui| {
+            if ui.button("預覽").clicked() {
+                match find_matches(manager, &self.query) {
+                    Ok(matches) => {
+                        self.matches = matches;
+                        status = Some(format!("找到{}個符合的段落", self.matches.len()));
+                    }
+                    Err(e) => status = Some(e),
+                }
+            }
+
+            if ui.button("全選").clicked() {
+                self.matches.iter_mut().for_each(|m| m.selected = true);
+            }
+
+            if ui.button("全不選").clicked() {
+                self.matches.iter_mut().for_each(|m| m.selected = false);
+            }
+
+            if ui.button("取代").clicked() {
+                // 整批取代只產生一筆編輯記錄
+                let ids = self.matches.iter().map(|m| m.key.id.as_str());
+                let snapshot = Snapshot::take(manager, ids);
+                let count = apply_matches(manager, &self.matches);
+                let label = format!("取代「{}」為「{}」", self.query.find, self.query.replace);
+                snapshot.record(manager, label);
+                self.matches.clear();
+                status = Some(format!("已取代{}個段落", count));
+            }
+        });
+
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .id_salt("replace_matches")
+            .show(ui, |ui| {
+                for found in self.matches.iter_mut() {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut found.selected, "");
+                        ui.label(format!("{} ({}處)", found.key.to_label(), found.count));
+                    });
+                    let ops = diff_tokens(&found.before, &found.after);
+                    ui.label(diff_layout(ui, &ops));
+                    ui.separator();
+                }
+            });
+
+        status
+    }
+
+    fn render_query(&mut self, ui: &mut egui::Ui) {
+        let query = &mut self.query;
+        egui::Grid::new("replace_query")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("尋找");
+                ui.text_edit_singleline(&mut query.find);
+                ui.end_row();
+                ui.label("取代為");
+                ui.text_edit_singleline(&mut query.replace);
+                ui.end_row();
+                ui.label("技能ID");
+                ui.add(
+                    egui::TextEdit::singleline(&mut query.id_pattern)
+                        .hint_text("可使用*與?，空白為全部"),
+                );
+                ui.end_row();
+
+                ui.label("欄位");
+                ui.horizontal(|ui| {
+                    for field_type in FieldType::ALL {
+                        toggle_filter(
+                            ui,
+                            &mut query.field_types,
+                            field_type.clone(),
+                            field_type.to_str(),
+                        );
+                    }
+                });
+                ui.end_row();
+
+                ui.label("資料表");
+                ui.horizontal(|ui| {
+                    for table in ObjectTable::ALL {
+                        toggle_filter(ui, &mut query.tables, table, table.to_str());
+                    }
+                });
+                ui.end_row();
+            });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut query.use_regex, "使用正規表示式");
+            ui.checkbox(&mut query.skip_color_codes, "略過色碼");
+        });
+    }
+}
+
+// 沒有勾選任何項目時代表不限制
+fn toggle_filter<T: PartialEq>(ui: &mut egui::Ui, selected: &mut Vec<T>, value: T, label: &str) {
+    let mut checked = selected.contains(&value);
+    if ui.checkbox(&mut checked, label).changed() {
+        if checked {
+            selected.push(value);
+        } else {
+            selected.retain(|v| v != &value);
+        }
+    }
+}
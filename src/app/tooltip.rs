@@ -1,26 +1,134 @@
-use crate::data::tooltip::{SkillData, SkillManager, TooltipData};
+use crate::app::comment::{CommentAction, CommentWindow};
+use crate::app::glossary::GlossaryWindow;
+use crate::app::history::{HistoryAction, HistoryWindow};
+use crate::app::import::ImportWindow;
+use crate::app::memory::MemoryPanel;
+use crate::app::merge::MergeWindow;
+use crate::app::orphan::OrphanWindow;
+use crate::app::qa::{QaAction, QaWindow};
+use crate::app::recovery::RecoveryWindow;
+use crate::app::remap::RemapWindow;
+use crate::app::replace::ReplaceWindow;
+use crate::app::update::UpdateWindow;
+use crate::data::tooltip::{
+    ReviewFlag, SegmentKey, SegmentStatus, SkillData, SkillManager, TooltipData,
+};
+use crate::ui::diff::{diff_layout, skeleton_diff_layout};
 use crate::ui::fonts::setup_custom_fonts;
-use crate::utils::common::FieldType;
+use crate::ui::highlight::{highlight_layout, show_highlight_tooltip, Highlight};
+use crate::utils::comment::Comments;
+use crate::utils::common::{
+    FieldType, AUTOSAVE_INTERVAL_SECONDS, FINGERPRINT_FILE_NAME, REVIEW_FLAG_FILE_NAME,
+    STATUS_FILE_NAME,
+};
+use crate::utils::diff::diff_tokens;
 use crate::utils::export::{export_files, export_translated};
+use crate::utils::fingerprint::{save_fingerprints, SourceFingerprints};
+use crate::utils::generate::generate_levels;
+use crate::utils::glossary::Glossary;
+use crate::utils::history::{self, Snapshot};
+use crate::utils::interchange::{export_po, export_xliff, PO_FILE_NAME, XLIFF_FILE_NAME};
+use crate::utils::markup::display_lines;
+use crate::utils::orphan::find_orphans;
 use crate::utils::parser;
+use crate::utils::propagate::{propagate_all, propagate_segment};
+use crate::utils::qa::check::{QaIssue, Severity};
+use crate::utils::qa::length::overflow_lines;
+use crate::utils::qa::settings::{LineBudget, QaSettings};
+use crate::utils::recovery::{remove_journal, write_journal, Journal};
+use crate::utils::reset::{fill_missing_fields, reset_level};
+use crate::utils::review::save_review_flags;
+use crate::utils::status::{
+    confirm_review_flag, ids_with_status, is_translated_text, mark_edited, save_statuses,
+    segment_status,
+};
 use eframe::egui;
+use std::collections::BTreeMap;
+
+const UNDO_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z);
+const REDO_SHORTCUT: egui::KeyboardShortcut =
+    egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Y);
+const REDO_SHIFT_SHORTCUT: egui::KeyboardShortcut = egui::KeyboardShortcut::new(
+    egui::Modifiers::COMMAND.plus(egui::Modifiers::SHIFT),
+    egui::Key::Z,
+);
 
 pub struct TooltipApp {
     data: TooltipData,
     status: String,
     search_text: String,
+    import_window: ImportWindow,
+    remap_window: RemapWindow,
+    memory_panel: MemoryPanel,
+    glossary_window: GlossaryWindow,
+    qa_window: QaWindow,
+    replace_window: ReplaceWindow,
+    update_window: UpdateWindow,
+    merge_window: MergeWindow,
+    orphan_window: OrphanWindow,
+    comment_window: CommentWindow,
+    active_segment: Option<SegmentKey>,
+    focus_request: Option<SegmentKey>,
+    auto_propagate: bool,
+    show_level_diff: bool,
+    // 只列出有此狀態段落的技能
+    status_filter: Option<SegmentStatus>,
+    // 匯出時低於此狀態的段落使用原文
+    export_status: SegmentStatus,
+    history_window: HistoryWindow,
+    // 編輯中的段落與開始編輯前的內容
+    editing: Option<(SegmentKey, Snapshot)>,
+    recovery_window: RecoveryWindow,
+    // 最後一次寫入翻譯檔或復原檔的內容
+    autosaved: Journal,
+    last_autosave: f64,
 }
 
 impl TooltipApp {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
         setup_custom_fonts(&cc.egui_ctx);
-        let data = parser::parse_tooltip_files();
-        let status = String::new();
+        let (data, mut warnings) = parser::parse_tooltip_files();
+        let recovery_window = RecoveryWindow::load(&data.skill_manager);
+        let autosaved = Journal::take(&data.skill_manager);
+        let stale = data.fingerprints.stale_keys(&data.skill_manager);
+        let orphans = find_orphans(&data.skill_manager);
+        if !stale.is_empty() {
+            warnings.push(format!("有{}個段落的原文在翻譯後已變更", stale.len()));
+        }
+        if !orphans.is_empty() {
+            warnings.push(format!(
+                "有{}筆翻譯在原文中已不存在，請至「工具 > 孤立翻譯」處理",
+                orphans.len()
+            ));
+        }
+        let status = warnings.join("；");
         let search_text = String::new();
         Self {
             data,
             status,
             search_text,
+            import_window: ImportWindow::default(),
+            remap_window: RemapWindow::default(),
+            memory_panel: MemoryPanel::default(),
+            glossary_window: GlossaryWindow::default(),
+            qa_window: QaWindow::default(),
+            replace_window: ReplaceWindow::default(),
+            update_window: UpdateWindow::default(),
+            merge_window: MergeWindow::default(),
+            orphan_window: OrphanWindow::default(),
+            comment_window: CommentWindow::default(),
+            active_segment: None,
+            focus_request: None,
+            auto_propagate: false,
+            show_level_diff: false,
+            status_filter: None,
+            export_status: SegmentStatus::Untranslated,
+            history_window: HistoryWindow::default(),
+            editing: None,
+            recovery_window,
+            autosaved,
+            last_autosave: 0.0,
         }
     }
 
@@ -81,10 +189,29 @@ impl TooltipApp {
     }
 
     fn render_skill_selector(&mut self, ui: &mut egui::Ui) {
+        let filter_text = match &self.status_filter {
+            Some(status) => status.to_str(),
+            None => "全部狀態",
+        };
+        egui::ComboBox::from_id_salt("status_filter")
+            .selected_text(filter_text)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.status_filter, None, "全部狀態");
+                for status in SegmentStatus::ALL {
+                    let label = status.to_str();
+                    ui.selectable_value(&mut self.status_filter, Some(status), label);
+                }
+            });
+
         egui::ComboBox::from_label("選擇技能")
             .selected_text(&self.data.current_id)
             .show_ui(ui, |ui| {
-                for id in self.data.skill_manager.get_skill_ids() {
+                let manager = &self.data.skill_manager;
+                let ids = match &self.status_filter {
+                    Some(status) => ids_with_status(manager, status),
+                    None => manager.get_skill_ids(),
+                };
+                for id in ids {
                     if ui
                         .selectable_value(&mut self.data.current_id, id.clone(), &id)
                         .clicked()
@@ -96,8 +223,19 @@ impl TooltipApp {
     }
 
     fn render_action_buttons(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_id_salt("export_status")
+            .selected_text(format!("匯出: {}以上", self.export_status.to_str()))
+            .show_ui(ui, |ui| {
+                for status in SegmentStatus::ALL {
+                    let label = format!("{}以上", status.to_str());
+                    ui.selectable_value(&mut self.export_status, status, label);
+                }
+            })
+            .response
+            .on_hover_text("狀態低於此設定的段落以原文匯出");
+
         if ui.button("資料匯出").clicked() {
-            match export_files(&self.data) {
+            match export_files(&self.data, &self.export_status) {
                 Ok(_) => self.update_status("匯出成功"),
                 Err(e) => self.update_status(format!("匯出失敗: {}", e)),
             }
@@ -105,7 +243,24 @@ impl TooltipApp {
 
         if ui.button("存檔翻譯").clicked() {
             match export_translated(&self.data) {
-                Ok(_) => self.update_status("存檔成功"),
+                Ok(_) => {
+                    self.autosaved = Journal::take(&self.data.skill_manager);
+                    let fingerprints = &mut self.data.fingerprints;
+                    fingerprints.fill_missing(&self.data.skill_manager);
+                    let manager = &self.data.skill_manager;
+                    let result = save_fingerprints(FINGERPRINT_FILE_NAME, fingerprints)
+                        .and_then(|_| save_statuses(STATUS_FILE_NAME, &manager.statuses))
+                        .and_then(|_| {
+                            save_review_flags(REVIEW_FLAG_FILE_NAME, &manager.review_flags)
+                        })
+                        .and_then(|_| remove_journal());
+                    match result {
+                        Ok(_) => self.update_status("存檔成功"),
+                        Err(e) => {
+                            self.update_status(format!("存檔成功，但寫入附屬檔案失敗: {}", e))
+                        }
+                    }
+                }
                 Err(e) => self.update_status(format!("存檔失敗: {}", e)),
             }
         }
@@ -118,13 +273,124 @@ impl TooltipApp {
                 .get(&self.data.current_id)
                 .cloned()
             {
-                self.data
-                    .skill_manager
-                    .translation_skills
-                    .insert(self.data.current_id.clone(), skill);
+                let manager = &mut self.data.skill_manager;
+                let id = self.data.current_id.as_str();
+                let snapshot = Snapshot::take(manager, [id]);
+                manager.translation_skills.insert(id.to_string(), skill);
+                snapshot.record(manager, format!("新增/重置[{}]的翻譯", id));
                 self.update_status("已新增/重置翻譯當前技能的翻譯內容");
             }
         }
+
+        if ui.button("補上缺少的欄位").clicked() {
+            let manager = &mut self.data.skill_manager;
+            let id = self.data.current_id.as_str();
+            let snapshot = Snapshot::take(manager, [id]);
+            let filled = fill_missing_fields(manager, id);
+            snapshot.record(manager, format!("補上[{}]缺少的欄位", id));
+            if filled.is_empty() {
+                self.update_status("沒有缺少的欄位");
+            } else {
+                let names: Vec<&str> = filled.iter().map(|f| f.to_str()).collect();
+                self.update_status(format!("已由原文補上欄位: {}", names.join("、")));
+            }
+        }
+
+        if ui.button("復原").clicked() {
+            self.undo();
+        }
+
+        if ui.button("重做").clicked() {
+            self.redo();
+        }
+
+        ui.toggle_value(&mut self.history_window.open, "編輯記錄");
+
+        ui.menu_button("工具", |ui| {
+            if ui.button("匯入參考翻譯").clicked() {
+                self.import_window.open = true;
+                ui.close_menu();
+            }
+
+            if ui.button("ID重新對應").clicked() {
+                self.remap_window.open = true;
+                ui.close_menu();
+            }
+
+            if ui.button("術語表").clicked() {
+                self.glossary_window.open = true;
+                ui.close_menu();
+            }
+
+            if ui.button("QA檢查").clicked() {
+                self.qa_window.open = true;
+                ui.close_menu();
+            }
+
+            if ui.button("尋找與取代").clicked() {
+                self.replace_window.open = true;
+                ui.close_menu();
+            }
+
+            if ui.button("原文版本更新").clicked() {
+                self.update_window.open = true;
+                ui.close_menu();
+            }
+
+            if ui.button("合併翻譯檔").clicked() {
+                self.merge_window.open = true;
+                ui.close_menu();
+            }
+
+            if ui.button("孤立翻譯").clicked() {
+                self.orphan_window.open = true;
+                self.orphan_window.refresh(&self.data.skill_manager);
+                ui.close_menu();
+            }
+
+            if ui.button("留言列表").clicked() {
+                self.comment_window.open = true;
+                ui.close_menu();
+            }
+
+            ui.separator();
+
+            if ui.button("匯出XLIFF").clicked() {
+                let manager = &self.data.skill_manager;
+                let (comments, language) = (&self.data.comments, &self.data.qa_settings.language);
+                match export_xliff(XLIFF_FILE_NAME, manager, comments, language) {
+                    Ok(_) => self.update_status(format!("已匯出{}", XLIFF_FILE_NAME)),
+                    Err(e) => self.update_status(format!("匯出失敗: {}", e)),
+                }
+                ui.close_menu();
+            }
+
+            if ui.button("匯出PO").clicked() {
+                let manager = &self.data.skill_manager;
+                let (comments, language) = (&self.data.comments, &self.data.qa_settings.language);
+                match export_po(PO_FILE_NAME, manager, comments, language) {
+                    Ok(_) => self.update_status(format!("已匯出{}", PO_FILE_NAME)),
+                    Err(e) => self.update_status(format!("匯出失敗: {}", e)),
+                }
+                ui.close_menu();
+            }
+
+            ui.separator();
+            ui.checkbox(&mut self.data.fingerprints.keep_source, "保存原文副本")
+                .on_hover_text("存檔時一併保存翻譯當時的原文，原文變更後可以比較差異");
+        });
+
+        ui.toggle_value(&mut self.memory_panel.open, "翻譯記憶");
+        ui.checkbox(&mut self.auto_propagate, "自動套用相同原文");
+        ui.checkbox(&mut self.show_level_diff, "顯示等級差異");
+
+        if ui.button("套用所有相同原文").clicked() {
+            let manager = &mut self.data.skill_manager;
+            let snapshot = Snapshot::take_all(manager);
+            let filled = propagate_all(manager);
+            snapshot.record(manager, "套用所有相同原文");
+            self.update_status(format!("已自動套用{}個相同原文的段落", filled.len()));
+        }
     }
 
     fn render_scroll_area(&mut self, ui: &mut egui::Ui) {
@@ -133,69 +399,339 @@ impl TooltipApp {
             .scroll_bar_visibility(egui::scroll_area::ScrollBarVisibility::AlwaysVisible)
             .show(ui, |ui| {
                 ui.vertical(|ui| {
-                    let manager = &mut self.data.skill_manager;
-                    render_skill_sections(ui, manager, &self.data.current_id);
+                    let focus_request = &mut self.focus_request;
+                    let issues = self.qa_window.issues();
+                    let status = &mut self.status;
+                    let show_level_diff = self.show_level_diff;
+                    let mut comment_request = None;
+                    let focused = render_skill_sections(
+                        ui,
+                        &mut self.data,
+                        focus_request,
+                        issues,
+                        status,
+                        show_level_diff,
+                        &mut comment_request,
+                    );
+                    if let Some(key) = comment_request {
+                        self.comment_window.open_for(key);
+                    }
+                    if let Some(focus) = focused {
+                        self.track_edit(&focus);
+                        if focus.finished {
+                            self.record_fingerprint(&focus.key);
+                        }
+                        if focus.finished && self.auto_propagate {
+                            let manager = &mut self.data.skill_manager;
+                            let snapshot = Snapshot::take_all(manager);
+                            let filled = propagate_segment(manager, &focus.key);
+                            snapshot.record(manager, "自動套用相同原文");
+                            if !filled.is_empty() {
+                                self.status = format!("已自動套用{}個相同原文的段落", filled.len());
+                            }
+                        }
+                        self.active_segment = Some(focus.key);
+                    }
                 });
             });
     }
 
+    // 段落開始編輯時保存原本內容，結束編輯時寫入一筆編輯記錄
+    fn track_edit(&mut self, focus: &SegmentFocus) {
+        if self.editing.as_ref().map(|(key, _)| key) != Some(&focus.key) {
+            self.finish_edit();
+            let snapshot = Snapshot::take(&self.data.skill_manager, [focus.key.id.as_str()]);
+            self.editing = Some((focus.key.clone(), snapshot));
+        }
+        if focus.finished {
+            self.finish_edit();
+        }
+    }
+
+    // 原文已變更的段落要由使用者確認後才更新
+    fn record_fingerprint(&mut self, key: &SegmentKey) {
+        let manager = &self.data.skill_manager;
+        let fingerprints = &mut self.data.fingerprints;
+        if !manager.is_translated(key) {
+            return;
+        }
+        if let Some(source) = manager.get_source(key) {
+            if !fingerprints.is_stale(key, source) {
+                fingerprints.record(key, source);
+            }
+        }
+    }
+
+    fn finish_edit(&mut self) {
+        if let Some((key, snapshot)) = self.editing.take() {
+            let manager = &mut self.data.skill_manager;
+            // 狀態與標記的變更和翻譯一起記錄，復原時一併還原
+            if snapshot.translation_changed(manager) {
+                mark_edited(manager, &key);
+                // 使用者修改過的翻譯不再是自動產生的內容
+                manager.review_flags.remove(&key);
+            }
+            snapshot.record(manager, format!("編輯{}", key.to_label()));
+        }
+    }
+
+    fn undo(&mut self) {
+        self.finish_edit();
+        let manager = &mut self.data.skill_manager;
+        match history::undo(manager, &self.data.current_id) {
+            Ok(label) => self.update_status(format!("已復原: {}", label)),
+            Err(e) => self.update_status(e),
+        }
+    }
+
+    fn redo(&mut self) {
+        self.finish_edit();
+        let manager = &mut self.data.skill_manager;
+        match history::redo(manager, &self.data.current_id) {
+            Ok(label) => self.update_status(format!("已重做: {}", label)),
+            Err(e) => self.update_status(e),
+        }
+    }
+
+    // 文字框編輯中時交給文字框處理自己的復原
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.memory(|m| m.focused().is_some()) {
+            return;
+        }
+        let (undo, redo) = ctx.input_mut(|i| {
+            let redo =
+                i.consume_shortcut(&REDO_SHIFT_SHORTCUT) || i.consume_shortcut(&REDO_SHORTCUT);
+            (i.consume_shortcut(&UNDO_SHORTCUT), redo)
+        });
+        if undo {
+            self.undo();
+        }
+        if redo {
+            self.redo();
+        }
+    }
+
+    // 定時把未存檔的翻譯、標記與狀態寫入復原檔，與translation.ini分開
+    fn autosave(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        ctx.request_repaint_after(std::time::Duration::from_secs_f64(
+            AUTOSAVE_INTERVAL_SECONDS,
+        ));
+        if now - self.last_autosave < AUTOSAVE_INTERVAL_SECONDS || self.recovery_window.is_pending()
+        {
+            return;
+        }
+        self.last_autosave = now;
+
+        let journal = Journal::take(&self.data.skill_manager);
+        if journal == self.autosaved {
+            return;
+        }
+        match write_journal(&journal) {
+            Ok(_) => self.autosaved = journal,
+            Err(e) => self.update_status(format!("自動存檔失敗: {}", e)),
+        }
+    }
+
     fn update_status(&mut self, str: impl Into<String>) {
         self.status = str.into();
     }
 }
 
-fn render_skill_sections(ui: &mut egui::Ui, manager: &mut SkillManager, id: &str) {
-    let (source_data, localized_data) = manager.get_data_mut(id);
+struct SegmentFocus {
+    key: SegmentKey,
+    // 本次畫面更新時結束編輯
+    finished: bool,
+}
+
+struct FieldFocus {
+    level: usize,
+    finished: bool,
+}
+
+struct SectionContext<'a> {
+    id: &'a str,
+    glossary: &'a Glossary,
+    settings: &'a QaSettings,
+    review_flags: &'a BTreeMap<SegmentKey, ReviewFlag>,
+    statuses: &'a BTreeMap<SegmentKey, SegmentStatus>,
+    focus_request: &'a mut Option<SegmentKey>,
+    issues: &'a [QaIssue],
+    fingerprints: &'a mut SourceFingerprints,
+    comments: &'a Comments,
+    // 原文欄位是否顯示與上一個等級的差異
+    show_level_diff: bool,
+    // 使用者要求修正等級數的欄位，在畫面繪製完後才套用
+    level_fix: Option<FieldType>,
+    // 要由第1級產生其他等級的欄位
+    level_generate: Option<FieldType>,
+    // 要以原文重置的等級
+    level_reset: Option<SegmentKey>,
+    // 要開啟留言的段落
+    comment_request: Option<SegmentKey>,
+    // 要確認自動標記的段落
+    review_confirm: Option<SegmentKey>,
+    // 使用者選擇的段落狀態
+    status_change: Option<(SegmentKey, SegmentStatus)>,
+}
+
+fn render_skill_sections(
+    ui: &mut egui::Ui,
+    data: &mut TooltipData,
+    focus_request: &mut Option<SegmentKey>,
+    issues: &[QaIssue],
+    status: &mut String,
+    show_level_diff: bool,
+    comment_request: &mut Option<SegmentKey>,
+) -> Option<SegmentFocus> {
+    let id = data.current_id.as_str();
+    let SkillManager {
+        skills,
+        translation_skills,
+        review_flags,
+        statuses,
+        ..
+    } = &mut data.skill_manager;
 
     let mut temp_skill = SkillData::default();
-    let localized = localized_data.unwrap_or(&mut temp_skill);
+    let localized = translation_skills.get_mut(id).unwrap_or(&mut temp_skill);
+
+    let mut context = SectionContext {
+        id,
+        glossary: &data.glossary,
+        settings: &data.qa_settings,
+        review_flags,
+        statuses,
+        focus_request,
+        issues,
+        fingerprints: &mut data.fingerprints,
+        comments: &data.comments,
+        show_level_diff,
+        level_fix: None,
+        level_generate: None,
+        level_reset: None,
+        comment_request: None,
+        review_confirm: None,
+        status_change: None,
+    };
 
-    if let Some(data) = source_data {
+    let focused = if let Some(source) = skills.get_mut(id) {
         ui.spacing_mut().item_spacing.y = 16.0;
-        show_split_section(
-            ui,
-            FieldType::Researchtip,
-            "researchtip_section",
-            &mut data.researchtip,
-            &mut localized.researchtip,
-        );
-        show_split_section(
-            ui,
-            FieldType::Researchubertip,
-            "researchubertip_section",
-            &mut data.researchubertip,
-            &mut localized.researchubertip,
-        );
-        show_split_section(
-            ui,
-            FieldType::Tip,
-            "tip_section",
-            &mut data.tip,
-            &mut localized.tip,
-        );
-        show_split_section(
-            ui,
-            FieldType::Ubertip,
-            "ubertip_section",
-            &mut data.ubertip,
-            &mut localized.ubertip,
-        );
+        let focused = [
+            show_split_section(
+                ui,
+                &mut context,
+                FieldType::Researchtip,
+                "researchtip_section",
+                &mut source.researchtip,
+                &mut localized.researchtip,
+            ),
+            show_split_section(
+                ui,
+                &mut context,
+                FieldType::Researchubertip,
+                "researchubertip_section",
+                &mut source.researchubertip,
+                &mut localized.researchubertip,
+            ),
+            show_split_section(
+                ui,
+                &mut context,
+                FieldType::Tip,
+                "tip_section",
+                &mut source.tip,
+                &mut localized.tip,
+            ),
+            show_split_section(
+                ui,
+                &mut context,
+                FieldType::Ubertip,
+                "ubertip_section",
+                &mut source.ubertip,
+                &mut localized.ubertip,
+            ),
+        ];
+        focused.into_iter().flatten().next()
     } else {
         ui.add_space(32.0);
         ui.label("無法讀取資料");
         ui.add_space(32.0);
+        None
+    };
+
+    let (level_fix, level_generate, level_reset, review_confirm, status_change) = (
+        context.level_fix,
+        context.level_generate,
+        context.level_reset,
+        context.review_confirm,
+        context.status_change,
+    );
+    *comment_request = context.comment_request;
+    let manager = &mut data.skill_manager;
+    if let Some(field_type) = level_fix {
+        let snapshot = Snapshot::take(manager, [id]);
+        manager.sync_levels(id, &field_type);
+        snapshot.record(
+            manager,
+            format!("修正[{}] {}的等級數", id, field_type.to_str()),
+        );
+    }
+    if let Some(key) = review_confirm {
+        let snapshot = Snapshot::take(manager, [id]);
+        confirm_review_flag(&mut manager.review_flags, &mut manager.statuses, &key);
+        snapshot.record(manager, format!("確認{}", key.to_label()));
+    }
+    if let Some((key, selected)) = status_change {
+        let snapshot = Snapshot::take(manager, [id]);
+        let label = format!("將{}設為{}", key.to_label(), selected.to_str());
+        manager.statuses.insert(key, selected);
+        snapshot.record(manager, label);
+    }
+    if let Some(key) = level_reset {
+        let snapshot = Snapshot::take(manager, [id]);
+        if reset_level(manager, &key) {
+            *status = format!("已以原文重置{}", key.to_label());
+        }
+        snapshot.record(manager, format!("以原文重置{}", key.to_label()));
     }
+    if let Some(field_type) = level_generate {
+        let snapshot = Snapshot::take(manager, [id]);
+        *status = match generate_levels(manager, id, &field_type) {
+            Ok(result) if result.skipped.is_empty() => {
+                format!("已由第1級產生{}個等級的翻譯", result.generated.len())
+            }
+            Ok(result) => {
+                let levels: Vec<String> = result
+                    .skipped
+                    .iter()
+                    .map(|key| (key.level + 1).to_string())
+                    .collect();
+                format!(
+                    "已由第1級產生{}個等級的翻譯，第{}級已有翻譯所以沒有覆蓋，需要重新產生請先以原文重置該等級",
+                    result.generated.len(),
+                    levels.join("、")
+                )
+            }
+            Err(e) => format!("無法產生等級: {}", e),
+        };
+        snapshot.record(
+            manager,
+            format!("由第1級產生[{}] {}", id, field_type.to_str()),
+        );
+    }
+    focused
 }
 
 fn show_split_section(
     ui: &mut egui::Ui,
+    context: &mut SectionContext,
     field_type: FieldType,
     section_id: &str,
     source_data: &mut Vec<String>,
     localized_data: &mut Vec<String>,
-) {
+) -> Option<SegmentFocus> {
     if source_data.is_empty() {
-        return;
+        return None;
     }
 
     ui.push_id(section_id, |ui| {
@@ -205,65 +741,477 @@ fn show_split_section(
             .rounding(egui::Rounding::same(4.0))
             .fill(ui.style().visuals.extreme_bg_color);
 
-        frame.show(ui, |ui| {
-            ui.vertical(|ui| {
-                ui.heading(field_type.to_str());
-                ui.separator();
-                ui.add_space(4.0);
-                render_split_columns(ui, source_data, localized_data);
-            });
-        });
+        frame
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.heading(field_type.to_str());
+                        render_comment_badge(ui, context, &field_type);
+                        if source_data.len() > 1 && ui.button("由第1級產生其他等級").clicked()
+                        {
+                            context.level_generate = Some(field_type.clone());
+                        }
+                    });
+                    ui.separator();
+                    if !localized_data.is_empty() && localized_data.len() != source_data.len() {
+                        render_level_warning(
+                            ui,
+                            context,
+                            &field_type,
+                            source_data.len(),
+                            localized_data.len(),
+                        );
+                    }
+                    ui.add_space(4.0);
+                    render_split_columns(ui, context, &field_type, source_data, localized_data)
+                })
+                .inner
+            })
+            .inner
+    })
+    .inner
+}
+
+// 欄位中有留言時顯示數量，有未解決的留言時以警告色顯示，點擊開啟第一個未解決的討論
+fn render_comment_badge(ui: &mut egui::Ui, context: &mut SectionContext, field_type: &FieldType) {
+    let (total, unresolved) = context.comments.count(context.id, field_type);
+    if total == 0 {
+        return;
+    }
+
+    let text = match unresolved {
+        0 => egui::RichText::new(format!("留言 {}", total)).weak(),
+        _ => egui::RichText::new(format!("未解決留言 {}/{}", unresolved, total))
+            .color(ui.visuals().warn_fg_color),
+    };
+    if ui.add(egui::Button::new(text).small()).clicked() {
+        let keys = context.comments.keys(unresolved > 0);
+        context.comment_request = keys
+            .into_iter()
+            .find(|key| key.id == context.id && &key.field_type == field_type);
+    }
+}
+
+fn render_level_warning(
+    ui: &mut egui::Ui,
+    context: &mut SectionContext,
+    field_type: &FieldType,
+    source_len: usize,
+    translation_len: usize,
+) {
+    ui.horizontal(|ui| {
+        ui.colored_label(
+            ui.visuals().warn_fg_color,
+            format!(
+                "原文有{}個等級，翻譯有{}個等級",
+                source_len, translation_len
+            ),
+        );
+        let label = if source_len > translation_len {
+            "補上原文新等級"
+        } else {
+            "刪除多餘等級"
+        };
+        if ui.button(label).clicked() {
+            context.level_fix = Some(field_type.clone());
+        }
     });
 }
 
 fn render_split_columns(
     ui: &mut egui::Ui,
+    context: &mut SectionContext,
+    field_type: &FieldType,
     source_data: &mut Vec<String>,
     localized_data: &mut Vec<String>,
-) {
+) -> Option<SegmentFocus> {
     let column_width = (ui.available_width() - 20.0) / 2.0;
+    let key = |level| SegmentKey::new(context.id, field_type.clone(), level);
+    let glossary = context.glossary;
+    let issues = context.issues;
+    let term_color = ui.visuals().selection.bg_fill.gamma_multiply(0.6);
+    let issue_color = ui.visuals().error_fg_color.gamma_multiply(0.4);
+    let overflow_color = ui.visuals().warn_fg_color.gamma_multiply(0.3);
+    let budget = context.settings.budget(field_type);
 
     egui::Grid::new("split_content")
         .num_columns(2)
         .spacing([20.0, 0.0])
         .show(ui, |ui| {
-            render_column(ui, column_width, source_data, false);
-            render_column(ui, column_width, localized_data, true);
+            render_column(
+                ui,
+                column_width,
+                source_data,
+                false,
+                context.show_level_diff,
+                |_, text| glossary_highlights(glossary, text, term_color),
+                |_, _, _, _| {},
+            );
+            let focused = render_column(
+                ui,
+                column_width,
+                localized_data,
+                true,
+                false,
+                |level, text| {
+                    let mut highlights = issue_highlights(issues, &key(level), issue_color);
+                    if let Some(budget) = budget {
+                        highlights.extend(overflow_highlights(text, budget, overflow_color));
+                        highlights.sort_by_key(|h| h.range.start);
+                    }
+                    highlights
+                },
+                |ui, level, text, response| {
+                    let key = key(level);
+                    if context.focus_request.as_ref() == Some(&key) {
+                        response.request_focus();
+                        response.scroll_to_me(Some(egui::Align::Center));
+                        *context.focus_request = None;
+                    }
+                    if let Some(budget) = budget {
+                        render_width_meter(ui, text, budget);
+                    }
+                    render_review_flag(ui, context, &key);
+                    if let Some(source) = source_data.get(level) {
+                        render_status(ui, context, &key, source, text);
+                        render_stale_source(ui, context.fingerprints, &key, source);
+                    }
+                    render_issues(ui, issues, &key);
+                    ui.horizontal(|ui| {
+                        if ui.small_button("以原文重置此等級").clicked() {
+                            context.level_reset = Some(key.clone());
+                        }
+                        let count = context.comments.thread(&key).len();
+                        let label = match count {
+                            0 => "留言".to_string(),
+                            count => format!("留言({})", count),
+                        };
+                        if ui.small_button(label).clicked() {
+                            context.comment_request = Some(key);
+                        }
+                    });
+                },
+            );
             ui.end_row();
+            focused.map(|focus| SegmentFocus {
+                key: key(focus.level),
+                finished: focus.finished,
+            })
+        })
+        .inner
+}
+
+fn glossary_highlights(glossary: &Glossary, text: &str, color: egui::Color32) -> Vec<Highlight> {
+    glossary
+        .find_terms(text)
+        .into_iter()
+        .map(|(range, entry)| {
+            let mut hover = format!("{} → {}", entry.term, entry.target);
+            if !entry.notes.is_empty() {
+                hover.push_str(&format!("\n{}", entry.notes));
+            }
+            Highlight {
+                range,
+                color,
+                hover: Some(hover),
+            }
+        })
+        .collect()
+}
+
+fn issue_highlights(issues: &[QaIssue], key: &SegmentKey, color: egui::Color32) -> Vec<Highlight> {
+    let mut highlights: Vec<Highlight> = issues
+        .iter()
+        .filter(|issue| &issue.key == key)
+        .filter_map(|issue| {
+            Some(Highlight {
+                range: issue.range.clone()?,
+                color,
+                hover: Some(issue.message.clone()),
+            })
+        })
+        .collect();
+    highlights.sort_by_key(|h| h.range.start);
+    highlights
+}
+
+fn overflow_highlights(text: &str, budget: &LineBudget, color: egui::Color32) -> Vec<Highlight> {
+    overflow_lines(text, budget)
+        .into_iter()
+        .map(|line| Highlight {
+            range: line.range,
+            color,
+            hover: Some(format!("寬度{}，超過上限{}", line.width, budget.max_width)),
+        })
+        .collect()
+}
+
+fn render_width_meter(ui: &mut egui::Ui, text: &str, budget: &LineBudget) {
+    if budget.max_lines == 0 && budget.max_width == 0 {
+        return;
+    }
+    let lines = display_lines(text);
+    let width = lines
+        .iter()
+        .map(|line| line.width)
+        .max()
+        .unwrap_or_default();
+    let over_lines = budget.max_lines > 0 && lines.len() > budget.max_lines;
+    let over_width = budget.max_width > 0 && width > budget.max_width;
+
+    let format_limit = |value: usize, limit: usize| match limit {
+        0 => value.to_string(),
+        _ => format!("{}/{}", value, limit),
+    };
+    let label = format!(
+        "行數 {}  最大寬度 {}",
+        format_limit(lines.len(), budget.max_lines),
+        format_limit(width, budget.max_width)
+    );
+    let fill = if over_lines || over_width {
+        ui.visuals().warn_fg_color
+    } else {
+        ui.visuals().selection.bg_fill
+    };
+    let progress = match budget.max_width {
+        0 => 0.0,
+        max_width => (width as f32 / max_width as f32).min(1.0),
+    };
+    ui.add(
+        egui::ProgressBar::new(progress)
+            .desired_height(14.0)
+            .fill(fill)
+            .text(label),
+    );
+}
+
+fn render_issues(ui: &mut egui::Ui, issues: &[QaIssue], key: &SegmentKey) {
+    for issue in issues.iter().filter(|issue| &issue.key == key) {
+        let color = match issue.severity {
+            Severity::Error => ui.visuals().error_fg_color,
+            _ => ui.visuals().warn_fg_color,
+        };
+        ui.colored_label(
+            color,
+            format!("[{}] {}", issue.rule.to_str(), issue.message),
+        );
+    }
+}
+
+fn render_review_flag(ui: &mut egui::Ui, context: &mut SectionContext, key: &SegmentKey) {
+    let Some(label) = context.review_flags.get(key).map(|flag| flag.to_str()) else {
+        return;
+    };
+
+    ui.horizontal(|ui| {
+        ui.colored_label(ui.visuals().warn_fg_color, label);
+        if ui.small_button("確認").clicked() {
+            context.review_confirm = Some(key.clone());
+        }
+    });
+}
+
+fn render_status(
+    ui: &mut egui::Ui,
+    context: &mut SectionContext,
+    key: &SegmentKey,
+    source: &str,
+    text: &str,
+) {
+    let status = context.statuses.get(key);
+    let translated = is_translated_text(source, text, status);
+    let current = segment_status(translated, status, context.review_flags.get(key));
+    // 與原文相同的翻譯也可以設定狀態，標示為刻意保留原文
+    if text.is_empty() {
+        ui.weak(current.to_str());
+        return;
+    }
+
+    let mut selected = current.clone();
+    egui::ComboBox::from_id_salt(("segment_status", key))
+        .selected_text(current.to_str())
+        .show_ui(ui, |ui| {
+            for status in SegmentStatus::ALL {
+                let label = status.to_str();
+                ui.selectable_value(&mut selected, status, label);
+            }
         });
+    if selected != current {
+        context.status_change = Some((key.clone(), selected));
+    }
 }
 
-fn render_column(ui: &mut egui::Ui, width: f32, items: &mut Vec<String>, is_editable: bool) {
+fn render_stale_source(
+    ui: &mut egui::Ui,
+    fingerprints: &mut SourceFingerprints,
+    key: &SegmentKey,
+    source: &str,
+) {
+    if !fingerprints.is_stale(key, source) {
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        ui.colored_label(ui.visuals().warn_fg_color, "原文在翻譯後已變更");
+        if ui.small_button("已依新原文更新").clicked() {
+            fingerprints.record(key, source);
+        }
+    });
+    if let Some(old) = fingerprints.old_source(key) {
+        let ops = diff_tokens(old, source);
+        egui::CollapsingHeader::new("原文差異")
+            .id_salt(("stale_source", key))
+            .show(ui, |ui| {
+                ui.label(format!("舊原文: {}", old));
+                ui.label(format!("新原文: {}", source));
+                ui.label(diff_layout(ui, &ops));
+            });
+    }
+}
+
+fn render_column(
+    ui: &mut egui::Ui,
+    width: f32,
+    items: &mut Vec<String>,
+    is_editable: bool,
+    diff_previous: bool,
+    highlight: impl Fn(usize, &str) -> Vec<Highlight>,
+    mut render_level: impl FnMut(&mut egui::Ui, usize, &str, &egui::Response),
+) -> Option<FieldFocus> {
     let is_ime_active = ui.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Ime(_))));
 
     ui.vertical(|ui| {
         ui.set_width(width);
         ui.add_space(4.0);
 
-        for i in 0..items.len() {
-            let mut item = items[i].clone();
+        let mut focused = None;
+        let mut previous: Option<String> = None;
+        for (i, value) in items.iter_mut().enumerate() {
+            // 以上一個等級為基準顯示差異，取代原本的文字框
+            if let Some(previous) = previous.replace(value.clone()).filter(|_| diff_previous) {
+                let ops = diff_tokens(&previous, value);
+                let response = egui::Frame::group(ui.style())
+                    .show(ui, |ui| {
+                        ui.set_width(ui.available_width());
+                        ui.label(skeleton_diff_layout(ui, &ops))
+                    })
+                    .inner;
+                render_level(ui, i, value, &response);
+                continue;
+            }
+
+            let mut item = value.clone();
+            let highlights = highlight(i, &item);
+            let mut layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+                let mut job = highlight_layout(ui, text, &highlights);
+                job.wrap.max_width = wrap_width;
+                ui.fonts(|f| f.layout_job(job))
+            };
 
-            let text_edit = egui::TextEdit::multiline(&mut item)
+            let mut text_edit = egui::TextEdit::multiline(&mut item)
                 .desired_width(ui.available_width())
                 .frame(true)
                 .interactive(is_editable);
+            if !highlights.is_empty() {
+                text_edit = text_edit.layouter(&mut layouter);
+            }
 
-            let response = ui.add(text_edit);
+            let output = text_edit.show(ui);
+            let response = &output.response;
+            show_highlight_tooltip(ui, &output, value, &highlights);
             if response.changed() {
                 let should_update =
                     !is_ime_active || !ui.input(|i| i.key_pressed(egui::Key::Enter));
                 if should_update {
-                    items[i] = item;
+                    *value = item;
                 }
             }
+
+            if is_editable && (response.has_focus() || response.lost_focus()) {
+                focused = Some(FieldFocus {
+                    level: i,
+                    finished: response.lost_focus(),
+                });
+            }
+
+            render_level(ui, i, value, response);
         }
-    });
+        focused
+    })
+    .inner
 }
 
 impl eframe::App for TooltipApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.handle_shortcuts(ctx);
         self.render_top_panel(ctx);
         self.render_bottom_panel(ctx);
+
+        let manager = &mut self.data.skill_manager;
+        let active = self.active_segment.as_ref();
+        if let Some(status) = self.memory_panel.show(ctx, manager, active) {
+            self.update_status(status);
+        }
+
         self.render_central_panel(ctx);
+
+        let manager = &mut self.data.skill_manager;
+        let status = [
+            self.import_window.show(ctx, manager),
+            self.remap_window.show(ctx, manager),
+            self.replace_window.show(ctx, manager),
+            self.merge_window.show(ctx, manager),
+            self.orphan_window.show(
+                ctx,
+                manager,
+                &mut self.data.fingerprints,
+                &mut self.data.comments,
+            ),
+            self.glossary_window.show(ctx, &mut self.data.glossary),
+        ];
+        if let Some(status) = status.into_iter().flatten().last() {
+            self.update_status(status);
+        }
+
+        let manager = &mut self.data.skill_manager;
+        if let Some(status) = self.recovery_window.show(ctx, manager) {
+            self.update_status(status);
+        }
+
+        if let Some(status) = self.update_window.show(ctx, &mut self.data) {
+            self.update_status(status);
+        }
+
+        let history = &mut self.data.skill_manager.history;
+        match self
+            .history_window
+            .show(ctx, history, &self.data.current_id)
+        {
+            Some(HistoryAction::Undo) => self.undo(),
+            Some(HistoryAction::Redo) => self.redo(),
+            None => {}
+        }
+
+        match self.comment_window.show(ctx, &mut self.data.comments) {
+            Some(CommentAction::Select(key)) => {
+                self.data.current_id = key.id.clone();
+                self.active_segment = Some(key.clone());
+                self.focus_request = Some(key);
+            }
+            Some(CommentAction::Status(status)) => self.update_status(status),
+            None => {}
+        }
+
+        match self.qa_window.show(ctx, &mut self.data) {
+            Some(QaAction::Select(key)) => {
+                self.data.current_id = key.id.clone();
+                self.active_segment = Some(key.clone());
+                self.focus_request = Some(key);
+            }
+            Some(QaAction::Status(status)) => self.update_status(status),
+            None => {}
+        }
+
+        self.autosave(ctx);
     }
 }
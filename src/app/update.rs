@@ -0,0 +1,178 @@
+use crate::data::tooltip::{SkillData, TooltipData};
+use crate::utils::archive::archive_translations;
+use crate::utils::common::{ARCHIVE_FILE_NAME, FINGERPRINT_FILE_NAME};
+use crate::utils::fingerprint::save_fingerprints;
+use crate::utils::parser::parse_file;
+use crate::utils::update::*;
+use eframe::egui;
+use std::collections::BTreeMap;
+
+pub struct UpdateWindow {
+    pub open: bool,
+    source_path: String,
+    new_skills: Option<BTreeMap<String, SkillData>>,
+    changes: Vec<SourceChange>,
+    options: UpdateOptions,
+    summary: Option<UpdateSummary>,
+}
+
+impl Default for UpdateWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            source_path: UPDATE_SOURCE_FILE_NAME.to_string(),
+            new_skills: None,
+            changes: vec![],
+            options: UpdateOptions::default(),
+            summary: None,
+        }
+    }
+}
+
+impl UpdateWindow {
+    pub fn show(&mut self, ctx: &egui::Context, data: &mut TooltipData) -> Option<String> {
+        let mut open = self.open;
+        let mut status = None;
+
+        egui::Window::new("原文版本更新")
+            .open(&mut open)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                status = self.render_content(ui, data);
+            });
+
+        self.open = open;
+        status
+    }
+
+    fn render_content(&mut self, ui: &mut egui::Ui, data: &mut TooltipData) -> Option<String> {
+        let mut status = None;
+
+        ui.horizontal(|ui| {
+            ui.label("新版原文");
+            ui.text_edit_singleline(&mut self.source_path);
+            if ui.button("比對").clicked() {
+                match parse_file(&self.source_path) {
+                    Ok(new_skills) => {
+                        self.changes = compare_sources(&data.skill_manager.skills, &new_skills);
+                        self.new_skills = Some(new_skills);
+                        self.summary = None;
+                        status = Some(format!("原文有{}處變動", self.changes.len()));
+                    }
+                    Err(e) => status = Some(format!("讀取新版原文失敗: {}", e)),
+                }
+            }
+        });
+
+        if let Some(summary) = &self.summary {
+            ui.separator();
+            render_summary(ui, summary);
+            return status;
+        }
+        if self.new_skills.is_none() {
+            return status;
+        }
+
+        ui.separator();
+        ui.checkbox(&mut self.options.mark_stale, "原文變更的翻譯標記為過時");
+        ui.checkbox(
+            &mut self.options.archive_removed,
+            format!("刪除的翻譯移到{}", ARCHIVE_FILE_NAME),
+        );
+        ui.checkbox(&mut self.options.queue_added, "新增的段落加入待翻譯");
+        ui.colored_label(
+            ui.visuals().warn_fg_color,
+            "原文更新無法復原，套用後會清除所有復原記錄",
+        );
+        if ui.button("套用更新").clicked() {
+            status = Some(match self.apply(data) {
+                Ok(_) => "已更新原文".to_string(),
+                Err(e) => format!("更新原文失敗: {}", e),
+            });
+        }
+
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .id_salt("update_changes")
+            .show(ui, |ui| {
+                for change in &self.changes {
+                    let target = match &change.field_type {
+                        Some(field_type) => field_type.to_str(),
+                        None => "整個物件",
+                    };
+                    let color = match change.kind {
+                        ChangeKind::Added => ui.visuals().text_color(),
+                        ChangeKind::Removed => ui.visuals().error_fg_color,
+                        ChangeKind::Changed => ui.visuals().warn_fg_color,
+                    };
+                    ui.colored_label(
+                        color,
+                        format!("[{}] {} {}", change.id, target, change.kind.to_str()),
+                    );
+                }
+            });
+
+        status
+    }
+
+    fn apply(&mut self, data: &mut TooltipData) -> Result<(), String> {
+        let Some(new_skills) = self.new_skills.clone() else {
+            return Ok(());
+        };
+
+        // 先在記憶體中更新，寫入指紋並取代source.ini後才寫入封存檔，避免失敗後重試時重複封存，失敗時還原所有內容
+        let manager = &mut data.skill_manager;
+        let backup = (
+            manager.skills.clone(),
+            manager.translation_skills.clone(),
+            manager.review_flags.clone(),
+            manager.statuses.clone(),
+            data.fingerprints.clone(),
+        );
+        let summary = apply_update(manager, &mut data.fingerprints, new_skills, &self.options);
+        let result = save_fingerprints(FINGERPRINT_FILE_NAME, &data.fingerprints)
+            .and_then(|_| replace_source_file(&self.source_path))
+            .and_then(|_| {
+                archive_translations(ARCHIVE_FILE_NAME, summary.archived.clone()).inspect_err(
+                    |_| {
+                        let _ = restore_source_file();
+                    },
+                )
+            });
+        if let Err(e) = result {
+            let manager = &mut data.skill_manager;
+            (
+                manager.skills,
+                manager.translation_skills,
+                manager.review_flags,
+                manager.statuses,
+                data.fingerprints,
+            ) = backup;
+            let _ = save_fingerprints(FINGERPRINT_FILE_NAME, &data.fingerprints);
+            return Err(e);
+        }
+
+        // 原文更新無法復原，更新前的記錄也不再適用
+        let manager = &mut data.skill_manager;
+        manager.history.clear();
+        if !manager.skills.contains_key(&data.current_id) {
+            data.current_id = manager.skills.keys().next().cloned().unwrap_or_default();
+        }
+        self.new_skills = None;
+        self.summary = Some(summary);
+        self.changes.clear();
+        Ok(())
+    }
+}
+
+fn render_summary(ui: &mut egui::Ui, summary: &UpdateSummary) {
+    ui.heading("更新結果");
+    ui.label(format!("原文未變更的翻譯: {}段", summary.unchanged));
+    ui.label(format!("原文變更需要確認的翻譯: {}段", summary.stale));
+    ui.label(format!("移到封存檔的物件: {}個", summary.archived.len()));
+    ui.label(format!(
+        "需要翻譯: {}段，共{}字",
+        summary.pending, summary.pending_chars
+    ));
+}
@@ -0,0 +1,122 @@
+use std::fs;
+use std::process::ExitCode;
+use wts_translation_manager::utils::common::TRANSLATE_FILE_NAME;
+use wts_translation_manager::utils::export::output_translated;
+use wts_translation_manager::utils::merge::*;
+use wts_translation_manager::utils::parser::parse_file;
+
+const USAGE: &str = "用法: wts-merge [選項] <翻譯檔案> <翻譯檔案> [<翻譯檔案>...]
+  --base <檔案>          共同的基準翻譯(預設 translation_base.ini)
+  --output <檔案>        合併結果(預設 translation.ini)
+  --prefer <編號>        衝突時採用第幾個翻譯檔案(由1開始)，未指定時有衝突就不輸出";
+
+struct Options {
+    base: String,
+    output: String,
+    prefer: Option<usize>,
+    edits: Vec<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        base: MERGE_BASE_FILE_NAME.to_string(),
+        output: TRANSLATE_FILE_NAME.to_string(),
+        prefer: None,
+        edits: vec![],
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("{} 缺少參數值", arg))
+        };
+        match arg.as_str() {
+            "--base" => options.base = value()?,
+            "--output" => options.output = value()?,
+            "--prefer" => {
+                let prefer = value()?;
+                let index = prefer
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|index| *index > 0)
+                    .ok_or_else(|| format!("無效的檔案編號: {}", prefer))?;
+                options.prefer = Some(index);
+            }
+            _ if arg.starts_with("--") => return Err(format!("未知的選項: {}", arg)),
+            _ => options.edits.push(arg.clone()),
+        }
+    }
+
+    if options.edits.len() < 2 {
+        return Err("至少需要兩個翻譯檔案".to_string());
+    }
+    if options
+        .prefer
+        .is_some_and(|index| index > options.edits.len())
+    {
+        return Err(format!("只有{}個翻譯檔案", options.edits.len()));
+    }
+    Ok(options)
+}
+
+fn print_conflicts(result: &MergeResult) {
+    for conflict in &result.conflicts {
+        eprintln!("衝突 {}", conflict.key.to_label());
+        for choice in &conflict.choices {
+            let value = choice.value.as_deref().unwrap_or("(無)");
+            eprintln!("  {}: {}", choice.label, value.replace('\n', "\\n"));
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<ExitCode, String> {
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return Ok(ExitCode::SUCCESS);
+    }
+    let options = parse_args(args)?;
+
+    let base = parse_file(&options.base)?;
+    let edits = options
+        .edits
+        .iter()
+        .map(|path| Ok((path.clone(), parse_file(path)?)))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut result = merge_translations(&base, &edits);
+    println!(
+        "自動合併{}個段落，{}個衝突",
+        result.auto_merged,
+        result.conflicts.len()
+    );
+    if let Some(index) = options.prefer {
+        // 選項的第一個是基準版本
+        result
+            .conflicts
+            .iter_mut()
+            .for_each(|conflict| conflict.selected = Some(index));
+    }
+    if result.unresolved() > 0 {
+        print_conflicts(&result);
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let output = output_translated(&result.merged_skills()?)?;
+    fs::write(&options.output, output).map_err(|e| format!("{}: {}", options.output, e))?;
+    println!("已輸出到{}", options.output);
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("{}", USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+use wts_translation_manager::data::tooltip::SkillManager;
+use wts_translation_manager::utils::common::*;
+use wts_translation_manager::utils::glossary::load_glossary;
+use wts_translation_manager::utils::parser::parse_file;
+use wts_translation_manager::utils::qa::check::{run_checks, QaContext, Severity};
+use wts_translation_manager::utils::qa::exception::load_exceptions;
+use wts_translation_manager::utils::qa::report::*;
+use wts_translation_manager::utils::qa::settings::load_settings;
+
+const USAGE: &str = "用法: wts-qa [選項]
+  --source <檔案>        原文檔案(預設 source.ini)
+  --translation <檔案>   翻譯檔案(預設 translation.ini)
+  --glossary <檔案>      術語表(預設 glossary.ini)
+  --settings <檔案>      QA設定(預設 qa_settings.ini)
+  --exceptions <檔案>    QA例外清單(預設 qa_exceptions.ini)
+  --format <格式>        text、json 或 junit(預設 text)
+  --fail-on <程度>       info、warning、error 或 none，達到此程度時結束代碼為1(預設 error)
+  --output <檔案>        輸出到檔案，未指定時輸出到標準輸出";
+
+struct Options {
+    source: String,
+    translation: String,
+    glossary: Option<String>,
+    settings: Option<String>,
+    exceptions: Option<String>,
+    format: ReportFormat,
+    threshold: Option<Severity>,
+    output: Option<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        source: SOURCE_FILE_NAME.to_string(),
+        translation: TRANSLATE_FILE_NAME.to_string(),
+        glossary: None,
+        settings: None,
+        exceptions: None,
+        format: ReportFormat::Text,
+        threshold: Some(Severity::Error),
+        output: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("{} 缺少參數值", arg))
+        };
+        match arg.as_str() {
+            "--source" => options.source = value()?,
+            "--translation" => options.translation = value()?,
+            "--glossary" => options.glossary = Some(value()?),
+            "--settings" => options.settings = Some(value()?),
+            "--exceptions" => options.exceptions = Some(value()?),
+            "--format" => {
+                let format = value()?;
+                options.format = ReportFormat::from_id(&format)
+                    .ok_or_else(|| format!("不支援的格式: {}", format))?;
+            }
+            "--fail-on" => {
+                let level = value()?;
+                options.threshold = match level.as_str() {
+                    "none" => None,
+                    _ => Some(
+                        Severity::from_id(&level)
+                            .ok_or_else(|| format!("不支援的嚴重程度: {}", level))?,
+                    ),
+                };
+            }
+            "--output" => options.output = Some(value()?),
+            _ => return Err(format!("未知的選項: {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+// 有指定檔案時讀取失敗視為錯誤，使用預設檔名時只有檔案不存在才用預設值
+fn load_optional<T: Default>(
+    path: &Option<String>,
+    default_path: &str,
+    load: impl Fn(&str) -> Result<T, String>,
+) -> Result<T, String> {
+    match path {
+        Some(path) => load(path),
+        None if !Path::new(default_path).exists() => Ok(T::default()),
+        None => load(default_path),
+    }
+}
+
+fn run(args: &[String]) -> Result<ExitCode, String> {
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", USAGE);
+        return Ok(ExitCode::SUCCESS);
+    }
+    let options = parse_args(args)?;
+
+    let manager = SkillManager {
+        skills: parse_file(&options.source)?,
+        translation_skills: parse_file(&options.translation)?,
+        ..Default::default()
+    };
+    let glossary = load_optional(&options.glossary, GLOSSARY_FILE_NAME, load_glossary)?;
+    let settings = load_optional(&options.settings, QA_SETTINGS_FILE_NAME, load_settings)?;
+    let exceptions = load_optional(&options.exceptions, QA_EXCEPTION_FILE_NAME, load_exceptions)?;
+
+    let context = QaContext {
+        glossary: &glossary,
+        exceptions: &exceptions,
+        settings: &settings,
+    };
+    let issues = run_checks(&manager, &context);
+    let report = format_report(options.format, &manager, &issues, options.threshold);
+    match &options.output {
+        Some(path) => fs::write(path, report).map_err(|e| format!("{}: {}", path, e))?,
+        None => print!("{}", report),
+    }
+
+    Ok(match exceeds_threshold(&issues, options.threshold) {
+        true => ExitCode::FAILURE,
+        false => ExitCode::SUCCESS,
+    })
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("{}", USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
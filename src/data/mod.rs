@@ -1 +1,3 @@
-pub mod tooltip;
\ No newline at end of file
+pub mod tooltip;
+#[cfg(test)]
+pub mod test_util;
\ No newline at end of file
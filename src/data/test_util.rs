@@ -0,0 +1,43 @@
+use crate::data::tooltip::{SkillData, SkillManager};
+use crate::utils::common::{ContextType, FieldType, TextType};
+use std::collections::BTreeMap;
+
+// 測試用的物件資料，所有欄位使用相同的文字格式
+pub fn skill(id: &str, text_type: TextType, fields: &[(FieldType, &[&str])]) -> SkillData {
+    let mut data = SkillData {
+        id: id.to_string(),
+        ..Default::default()
+    };
+    for (field_type, values) in fields {
+        data.insert_data(
+            text_type.clone(),
+            field_type.clone(),
+            values.iter().map(|s| s.to_string()).collect(),
+        );
+    }
+    data
+}
+
+// 只有Tip欄位的物件
+pub fn tip_skill(id: &str, tip: &[&str]) -> SkillData {
+    skill(id, TextType::SingleLineArray, &[(FieldType::Tip, tip)])
+}
+
+pub fn with_context(mut data: SkillData, context: &[(ContextType, &str)]) -> SkillData {
+    for (context_type, value) in context {
+        data.context.insert(context_type.clone(), value.to_string());
+    }
+    data
+}
+
+pub fn skill_map(skills: Vec<SkillData>) -> BTreeMap<String, SkillData> {
+    skills.into_iter().map(|s| (s.id.clone(), s)).collect()
+}
+
+pub fn manager(skills: Vec<SkillData>, translation_skills: Vec<SkillData>) -> SkillManager {
+    SkillManager {
+        skills: skill_map(skills),
+        translation_skills: skill_map(translation_skills),
+        ..Default::default()
+    }
+}
@@ -1,16 +1,32 @@
+use crate::utils::comment::Comments;
 use crate::utils::common::*;
+use crate::utils::fingerprint::SourceFingerprints;
+use crate::utils::glossary::Glossary;
+use crate::utils::history::History;
+use crate::utils::qa::exception::QaExceptions;
+use crate::utils::qa::settings::QaSettings;
+use crate::utils::status::{is_translated_text, segment_status};
 use std::collections::BTreeMap;
 
 #[derive(Default)]
 pub struct TooltipData {
     pub current_id: String,
     pub skill_manager: SkillManager,
+    pub glossary: Glossary,
+    pub qa_exceptions: QaExceptions,
+    pub qa_settings: QaSettings,
+    pub fingerprints: SourceFingerprints,
+    pub comments: Comments,
 }
 
 #[derive(Default)]
 pub struct SkillManager {
     pub skills: BTreeMap<String, SkillData>,
     pub translation_skills: BTreeMap<String, SkillData>,
+    pub review_flags: BTreeMap<SegmentKey, ReviewFlag>,
+    // 使用者設定的審校狀態，沒有設定的段落依翻譯內容判斷
+    pub statuses: BTreeMap<SegmentKey, SegmentStatus>,
+    pub history: History,
 }
 
 impl SkillManager {
@@ -23,9 +39,244 @@ impl SkillManager {
         ids.sort();
         ids
     }
+
+    pub fn get_source(&self, key: &SegmentKey) -> Option<&String> {
+        self.skills
+            .get(&key.id)?
+            .get_field(&key.field_type)
+            .get(key.level)
+    }
+
+    pub fn get_translation(&self, key: &SegmentKey) -> Option<&String> {
+        self.translation_skills
+            .get(&key.id)?
+            .get_field(&key.field_type)
+            .get(key.level)
+    }
+
+    pub fn get_translation_mut(&mut self, key: &SegmentKey) -> Option<&mut String> {
+        self.translation_skills
+            .get_mut(&key.id)?
+            .get_field_mut(&key.field_type)
+            .get_mut(key.level)
+    }
+
+    pub fn segment_keys(&self) -> Vec<SegmentKey> {
+        let mut keys = vec![];
+        for (id, data) in &self.skills {
+            for field_type in FieldType::ALL.iter() {
+                for level in 0..data.get_field(field_type).len() {
+                    keys.push(SegmentKey::new(id, field_type.clone(), level));
+                }
+            }
+        }
+        keys
+    }
+
+    // 翻譯不存在或空白視為未翻譯，與原文相同時依使用者設定的狀態判斷
+    pub fn is_translated(&self, key: &SegmentKey) -> bool {
+        match (self.get_source(key), self.get_translation(key)) {
+            (Some(source), Some(translation)) => {
+                is_translated_text(source, translation, self.statuses.get(key))
+            }
+            _ => false,
+        }
+    }
+
+    pub fn status(&self, key: &SegmentKey) -> SegmentStatus {
+        segment_status(
+            self.is_translated(key),
+            self.statuses.get(key),
+            self.review_flags.get(key),
+        )
+    }
+
+    // 寫入單一等級的翻譯，翻譯欄位不存在時以原文補齊其他等級
+    pub fn set_translation(&mut self, key: &SegmentKey, value: String) -> bool {
+        let Some(source) = self.skills.get(&key.id) else {
+            return false;
+        };
+        let Some(text_type) = source.text_type_map.get(&key.field_type).cloned() else {
+            return false;
+        };
+        let source_value = source.get_field(&key.field_type);
+        if key.level >= source_value.len() {
+            return false;
+        }
+
+        let translated = self
+            .translation_skills
+            .entry(key.id.clone())
+            .or_insert_with(|| SkillData {
+                id: key.id.clone(),
+                ..Default::default()
+            });
+        let field = translated.get_field_mut(&key.field_type);
+        if field.len() < source_value.len() {
+            field.extend_from_slice(&source_value[field.len()..]);
+        }
+        field[key.level] = value;
+        translated
+            .text_type_map
+            .entry(key.field_type.clone())
+            .or_insert(text_type);
+        true
+    }
+
+    // 翻譯欄位的等級數與原文不同時回傳(原文, 翻譯)的等級數
+    pub fn level_count_mismatch(&self, id: &str, field_type: &FieldType) -> Option<(usize, usize)> {
+        let source = self.skills.get(id)?.get_field(field_type).len();
+        let translation = self.translation_skills.get(id)?.get_field(field_type).len();
+        (translation != 0 && source != translation).then_some((source, translation))
+    }
+
+    // 補上原文新增的等級，或刪除翻譯多出的等級
+    pub fn sync_levels(&mut self, id: &str, field_type: &FieldType) -> bool {
+        let Some((source_len, _)) = self.level_count_mismatch(id, field_type) else {
+            return false;
+        };
+        let (Some(source), Some(translated)) =
+            (self.skills.get(id), self.translation_skills.get_mut(id))
+        else {
+            return false;
+        };
+
+        let source_value = source.get_field(field_type);
+        let field = translated.get_field_mut(field_type);
+        if field.len() < source_len {
+            field.extend_from_slice(&source_value[field.len()..]);
+        } else {
+            field.truncate(source_len);
+        }
+        // 等級數改變時文字格式也要跟著原文，匯出時才會輸出正確的格式
+        if let Some(text_type) = source.text_type_map.get(field_type) {
+            translated
+                .text_type_map
+                .insert(field_type.clone(), text_type.clone());
+        }
+        let removed = |key: &SegmentKey| {
+            key.id == id && &key.field_type == field_type && key.level >= source_len
+        };
+        self.review_flags.retain(|key, _| !removed(key));
+        self.statuses.retain(|key, _| !removed(key));
+        true
+    }
+}
+
+// 需要人工確認的自動翻譯標記
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReviewFlag {
+    AutoPropagated,
+    Generated,
+    Queued,
 }
 
-#[derive(Default, Debug, Clone)]
+impl ReviewFlag {
+    pub const ALL: [ReviewFlag; 3] = [
+        ReviewFlag::AutoPropagated,
+        ReviewFlag::Generated,
+        ReviewFlag::Queued,
+    ];
+
+    pub fn from_id(id: &str) -> Option<ReviewFlag> {
+        ReviewFlag::ALL.into_iter().find(|flag| flag.to_id() == id)
+    }
+
+    pub fn to_id(&self) -> &'static str {
+        match self {
+            ReviewFlag::AutoPropagated => "propagated",
+            ReviewFlag::Generated => "generated",
+            ReviewFlag::Queued => "queued",
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ReviewFlag::AutoPropagated => "自動套用",
+            ReviewFlag::Generated => "由第1級產生",
+            ReviewFlag::Queued => "版本更新待翻譯",
+        }
+    }
+}
+
+// 段落的審校狀態，依流程先後排序
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SegmentStatus {
+    Untranslated,
+    MachineFilled,
+    Draft,
+    Translated,
+    Reviewed,
+    Approved,
+}
+
+impl SegmentStatus {
+    pub const ALL: [SegmentStatus; 6] = [
+        SegmentStatus::Untranslated,
+        SegmentStatus::MachineFilled,
+        SegmentStatus::Draft,
+        SegmentStatus::Translated,
+        SegmentStatus::Reviewed,
+        SegmentStatus::Approved,
+    ];
+
+    pub fn from_id(id: &str) -> Option<SegmentStatus> {
+        SegmentStatus::ALL
+            .into_iter()
+            .find(|status| status.to_id() == id)
+    }
+
+    pub fn to_id(&self) -> &'static str {
+        match self {
+            SegmentStatus::Untranslated => "untranslated",
+            SegmentStatus::MachineFilled => "machine",
+            SegmentStatus::Draft => "draft",
+            SegmentStatus::Translated => "translated",
+            SegmentStatus::Reviewed => "reviewed",
+            SegmentStatus::Approved => "approved",
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            SegmentStatus::Untranslated => "未翻譯",
+            SegmentStatus::MachineFilled => "機器填入",
+            SegmentStatus::Draft => "草稿",
+            SegmentStatus::Translated => "已翻譯",
+            SegmentStatus::Reviewed => "已審校",
+            SegmentStatus::Approved => "已核准",
+        }
+    }
+}
+
+// 單一欄位中的單一等級文字
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct SegmentKey {
+    pub id: String,
+    pub field_type: FieldType,
+    pub level: usize,
+}
+
+impl SegmentKey {
+    pub fn new(id: &str, field_type: FieldType, level: usize) -> Self {
+        Self {
+            id: id.to_string(),
+            field_type,
+            level,
+        }
+    }
+
+    pub fn to_label(&self) -> String {
+        format!(
+            "[{}] {} #{}",
+            self.id,
+            self.field_type.to_str(),
+            self.level + 1
+        )
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct SkillData {
     pub id: String,
     pub researchtip: Vec<String>,
@@ -33,6 +284,7 @@ pub struct SkillData {
     pub tip: Vec<String>,
     pub ubertip: Vec<String>,
     pub text_type_map: BTreeMap<FieldType, TextType>,
+    pub context: BTreeMap<ContextType, String>,
 }
 
 impl SkillData {
@@ -59,4 +311,22 @@ impl SkillData {
 
         self.text_type_map.insert(field_type, text_type);
     }
+
+    pub fn get_field(&self, field_type: &FieldType) -> &Vec<String> {
+        match field_type {
+            FieldType::Researchtip => &self.researchtip,
+            FieldType::Researchubertip => &self.researchubertip,
+            FieldType::Tip => &self.tip,
+            FieldType::Ubertip => &self.ubertip,
+        }
+    }
+
+    pub fn get_field_mut(&mut self, field_type: &FieldType) -> &mut Vec<String> {
+        match field_type {
+            FieldType::Researchtip => &mut self.researchtip,
+            FieldType::Researchubertip => &mut self.researchubertip,
+            FieldType::Tip => &mut self.tip,
+            FieldType::Ubertip => &mut self.ubertip,
+        }
+    }
 }
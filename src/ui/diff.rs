@@ -0,0 +1,45 @@
+use crate::utils::diff::DiffOp;
+use eframe::egui;
+use eframe::egui::text::LayoutJob;
+
+pub fn diff_layout(ui: &egui::Ui, ops: &[DiffOp]) -> LayoutJob {
+    diff_layout_with(ui, ops, ui.visuals().text_color())
+}
+
+// 淡化沒有變化的部分，讓等級間變化的文字更明顯
+pub fn skeleton_diff_layout(ui: &egui::Ui, ops: &[DiffOp]) -> LayoutJob {
+    diff_layout_with(ui, ops, ui.visuals().weak_text_color())
+}
+
+fn diff_layout_with(ui: &egui::Ui, ops: &[DiffOp], equal_color: egui::Color32) -> LayoutJob {
+    let font_id = egui::TextStyle::Body.resolve(ui.style());
+    let visuals = ui.visuals();
+    let mut job = LayoutJob::default();
+
+    for op in ops {
+        let (text, format) = match op {
+            DiffOp::Equal(text) => (text, egui::TextFormat::simple(font_id.clone(), equal_color)),
+            DiffOp::Delete(text) => (
+                text,
+                egui::TextFormat {
+                    font_id: font_id.clone(),
+                    color: visuals.error_fg_color,
+                    strikethrough: egui::Stroke::new(1.0, visuals.error_fg_color),
+                    ..Default::default()
+                },
+            ),
+            DiffOp::Insert(text) => (
+                text,
+                egui::TextFormat {
+                    font_id: font_id.clone(),
+                    color: visuals.strong_text_color(),
+                    background: visuals.selection.bg_fill,
+                    ..Default::default()
+                },
+            ),
+        };
+        job.append(text, 0.0, format);
+    }
+
+    job
+}
@@ -0,0 +1,78 @@
+use eframe::egui;
+use eframe::egui::text::LayoutJob;
+use std::ops::Range;
+
+#[derive(Debug, Clone)]
+pub struct Highlight {
+    // 文字中的位元組位置
+    pub range: Range<usize>,
+    pub color: egui::Color32,
+    pub hover: Option<String>,
+}
+
+pub fn highlight_layout(ui: &egui::Ui, text: &str, highlights: &[Highlight]) -> LayoutJob {
+    let font_id = egui::TextStyle::Body.resolve(ui.style());
+    let text_color = ui
+        .visuals()
+        .override_text_color
+        .unwrap_or_else(|| ui.visuals().widgets.inactive.text_color());
+    let plain = egui::TextFormat::simple(font_id.clone(), text_color);
+
+    let mut job = LayoutJob::default();
+    let mut cursor = 0;
+    for highlight in highlights {
+        let range = &highlight.range;
+        // 編輯中的文字可能與位置不同步，略過失效的位置
+        if range.start < cursor || text.get(range.clone()).is_none() {
+            continue;
+        }
+        job.append(&text[cursor..range.start], 0.0, plain.clone());
+        job.append(
+            &text[range.clone()],
+            0.0,
+            egui::TextFormat {
+                background: highlight.color,
+                ..plain.clone()
+            },
+        );
+        cursor = range.end;
+    }
+    job.append(&text[cursor..], 0.0, plain);
+    job
+}
+
+pub fn show_highlight_tooltip(
+    ui: &egui::Ui,
+    output: &egui::text_edit::TextEditOutput,
+    text: &str,
+    highlights: &[Highlight],
+) {
+    if highlights.is_empty() || !output.response.hovered() {
+        return;
+    }
+    let Some(pointer) = ui.input(|i| i.pointer.hover_pos()) else {
+        return;
+    };
+
+    let char_index = output
+        .galley
+        .cursor_from_pos(pointer - output.galley_pos)
+        .ccursor
+        .index;
+    let Some((byte_index, _)) = text.char_indices().nth(char_index) else {
+        return;
+    };
+
+    let hover = highlights
+        .iter()
+        .find(|h| h.range.contains(&byte_index))
+        .and_then(|h| h.hover.as_ref());
+    if let Some(hover) = hover {
+        egui::show_tooltip_at_pointer(
+            ui.ctx(),
+            ui.layer_id(),
+            output.response.id.with("highlight"),
+            |ui| ui.label(hover),
+        );
+    }
+}
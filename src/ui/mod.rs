@@ -1 +1,3 @@
-pub mod fonts;
\ No newline at end of file
+pub mod diff;
+pub mod fonts;
+pub mod highlight;
\ No newline at end of file
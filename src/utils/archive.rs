@@ -0,0 +1,102 @@
+use crate::data::tooltip::SkillData;
+use crate::utils::common::FieldType;
+use crate::utils::export::output_translated;
+use crate::utils::parser::parse_file;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// 把不再使用的翻譯附加到封存檔，同一個欄位以新封存的內容為準
+pub fn archive_translations(
+    path: &str,
+    archived: BTreeMap<String, SkillData>,
+) -> Result<usize, String> {
+    if archived.is_empty() {
+        return Ok(0);
+    }
+    let mut skills = match Path::new(path).exists() {
+        true => parse_file(path)?,
+        false => BTreeMap::new(),
+    };
+    let count = archived.len();
+    merge_archive(&mut skills, archived);
+    let output = output_translated(&skills)?;
+    fs::write(path, output).map_err(|e| format!("{}: {}", path, e))?;
+    Ok(count)
+}
+
+pub fn merge_archive(
+    skills: &mut BTreeMap<String, SkillData>,
+    archived: BTreeMap<String, SkillData>,
+) {
+    for (id, data) in archived {
+        let entry = skills.entry(id.clone()).or_insert_with(|| SkillData {
+            id: id.clone(),
+            ..Default::default()
+        });
+        for field_type in FieldType::ALL.iter() {
+            let (Some(text_type), values) = (
+                data.text_type_map.get(field_type),
+                data.get_field(field_type),
+            ) else {
+                continue;
+            };
+            if !values.is_empty() {
+                entry.insert_data(text_type.clone(), field_type.clone(), values.clone());
+            }
+        }
+        entry.context.extend(data.context);
+    }
+}
+
+// 只包含單一欄位的翻譯資料，用來封存個別欄位
+pub fn field_data(data: &SkillData, field_type: &FieldType) -> Option<SkillData> {
+    let values = data.get_field(field_type);
+    let text_type = data.text_type_map.get(field_type)?;
+    if values.is_empty() {
+        return None;
+    }
+    let mut field = SkillData {
+        id: data.id.clone(),
+        ..Default::default()
+    };
+    field.insert_data(text_type.clone(), field_type.clone(), values.clone());
+    Some(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::skill;
+    use crate::utils::common::TextType;
+
+    #[test]
+    fn test_merge_archive() {
+        let old = skill("A000", TextType::SingleLine, &[(FieldType::Tip, &["舊的"])]);
+        let mut skills = BTreeMap::from([("A000".to_string(), old)]);
+        let archived = skill(
+            "A000",
+            TextType::SingleLine,
+            &[(FieldType::Tip, &["新的"]), (FieldType::Ubertip, &["說明"])],
+        );
+        // 物件ID與鍵值不同，用來確認合併時以封存的鍵值為準
+        let other = skill("A000", TextType::SingleLine, &[(FieldType::Tip, &["其他"])]);
+        merge_archive(
+            &mut skills,
+            BTreeMap::from([
+                ("A000".to_string(), archived.clone()),
+                ("A001".to_string(), other),
+            ]),
+        );
+
+        assert_eq!(skills.len(), 2);
+        assert_eq!(skills["A000"].tip, vec!["新的".to_string()]);
+        assert_eq!(skills["A000"].ubertip, vec!["說明".to_string()]);
+        assert_eq!(skills["A001"].id, "A001");
+
+        let field = field_data(&archived, &FieldType::Ubertip).unwrap();
+        assert!(field.tip.is_empty());
+        assert_eq!(field.ubertip, archived.ubertip);
+        assert!(field_data(&archived, &FieldType::Researchtip).is_none());
+    }
+}
@@ -0,0 +1,217 @@
+use crate::data::tooltip::SegmentKey;
+use crate::utils::common::*;
+use regex::Regex;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const COMMENT_ID_REGEX: &str = r#"^\[(.+)\]$"#;
+pub const COMMENT_REGEX: &str =
+    r#"^([A-Za-z]+)#(\d+)\s+(\d+)\s+(open|resolved)\s+"((?:[^"\\]|\\.)*)"\s*=\s*"(.*)"$"#;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub key: SegmentKey,
+    pub author: String,
+    // Unix時間(秒)
+    pub timestamp: u64,
+    pub text: String,
+    pub resolved: bool,
+}
+
+// 每個段落的留言依時間先後排列成一串討論
+#[derive(Default, Debug, Clone)]
+pub struct Comments {
+    pub entries: Vec<Comment>,
+}
+
+impl Comments {
+    pub fn thread(&self, key: &SegmentKey) -> Vec<&Comment> {
+        self.entries.iter().filter(|c| &c.key == key).collect()
+    }
+
+    // 新的留言會讓整串討論回到未解決
+    pub fn add(&mut self, key: &SegmentKey, author: &str, text: &str, timestamp: u64) {
+        self.entries.push(Comment {
+            key: key.clone(),
+            author: author.to_string(),
+            timestamp,
+            text: text.to_string(),
+            resolved: false,
+        });
+    }
+
+    pub fn resolve(&mut self, key: &SegmentKey) {
+        self.entries
+            .iter_mut()
+            .filter(|c| &c.key == key)
+            .for_each(|c| c.resolved = true);
+    }
+
+    pub fn is_unresolved(&self, key: &SegmentKey) -> bool {
+        self.entries.iter().any(|c| &c.key == key && !c.resolved)
+    }
+
+    // 有留言的段落，依段落排序
+    pub fn keys(&self, unresolved_only: bool) -> Vec<SegmentKey> {
+        let mut keys: Vec<SegmentKey> = self
+            .entries
+            .iter()
+            .filter(|c| !unresolved_only || !c.resolved)
+            .map(|c| c.key.clone())
+            .collect();
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+
+    // (留言數, 未解決的留言數)
+    pub fn count(&self, id: &str, field_type: &FieldType) -> (usize, usize) {
+        self.entries
+            .iter()
+            .filter(|c| c.key.id == id && &c.key.field_type == field_type)
+            .fold((0, 0), |(total, open), c| {
+                (total + 1, open + usize::from(!c.resolved))
+            })
+    }
+}
+
+pub fn now_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+// 以UTC顯示，格式為 YYYY-MM-DD HH:MM
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let seconds = timestamp % 86400;
+
+    // 由1970-01-01起算的天數換算成日期
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+pub fn load_comments(path: &str) -> Result<Comments, String> {
+    let content = read_lossy(path)?;
+    Ok(parse_comments(&content))
+}
+
+pub fn save_comments(path: &str, comments: &Comments) -> Result<(), String> {
+    fs::write(path, output_comments(comments)).map_err(|e| e.to_string())
+}
+
+pub fn parse_comments(content: &str) -> Comments {
+    let id_pattern = Regex::new(COMMENT_ID_REGEX).unwrap();
+    let pattern = Regex::new(COMMENT_REGEX).unwrap();
+
+    let mut comments = Comments::default();
+    let mut current_id = None;
+    for line in content.lines() {
+        if let Some(caps) = id_pattern.captures(line) {
+            current_id = Some(caps[1].to_string());
+            continue;
+        }
+
+        let (Some(id), Some(caps)) = (&current_id, pattern.captures(line)) else {
+            continue;
+        };
+        let Some(field_type) = FieldType::from_str(&caps[1]) else {
+            continue;
+        };
+        let (Ok(level), Ok(timestamp)) = (caps[2].parse::<usize>(), caps[3].parse::<u64>()) else {
+            continue;
+        };
+        comments.entries.push(Comment {
+            key: SegmentKey::new(id, field_type, level.saturating_sub(1)),
+            author: unescape_value(&caps[5]),
+            timestamp,
+            text: unescape_value(&caps[6]),
+            resolved: &caps[4] == "resolved",
+        });
+    }
+
+    comments
+}
+
+pub fn output_comments(comments: &Comments) -> String {
+    let mut entries: Vec<&Comment> = comments.entries.iter().collect();
+    entries.sort_by_key(|c| &c.key.id);
+
+    let mut output = String::new();
+    let mut current_id: Option<&str> = None;
+    for comment in entries {
+        if current_id != Some(comment.key.id.as_str()) {
+            if current_id.is_some() {
+                output.push(NEWLINE_SYMBOL);
+            }
+            output.push_line(&format!("[{}]", comment.key.id));
+            current_id = Some(comment.key.id.as_str());
+        }
+        output.push_line(&format!(
+            "{}#{} {} {} \"{}\" = \"{}\"",
+            comment.key.field_type.to_str(),
+            comment.key.level + 1,
+            comment.timestamp,
+            if comment.resolved { "resolved" } else { "open" },
+            escape_value(&comment.author),
+            escape_value(&comment.text)
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(951782400 + 3661), "2000-02-29 01:01");
+        assert_eq!(format_timestamp(1700000000), "2023-11-14 22:13");
+    }
+
+    #[test]
+    fn test_comment_threads() {
+        let first = SegmentKey::new("A001", FieldType::Tip, 0);
+        let second = SegmentKey::new("A000", FieldType::Ubertip, 1);
+        let mut comments = Comments::default();
+        comments.add(&first, "reviewer", "英雄名稱待定", 1700000000);
+        comments.add(&second, "譯者 \"A\"", "第一行\n第二行", 1700000100);
+        comments.resolve(&first);
+        comments.add(&first, "translator", "改成\\暫定名稱", 1700000200);
+
+        assert!(comments.is_unresolved(&first));
+        comments.resolve(&second);
+        assert_eq!(comments.keys(true), vec![first.clone()]);
+        assert_eq!(comments.keys(false), vec![second.clone(), first.clone()]);
+        assert_eq!(comments.count("A001", &FieldType::Tip), (2, 1));
+
+        let content = output_comments(&comments);
+        assert_eq!(
+            content,
+            "[A000]\nUbertip#2 1700000100 resolved \"譯者 \\\"A\\\"\" = \"第一行\\n第二行\"\n\n[A001]\nTip#1 1700000000 resolved \"reviewer\" = \"英雄名稱待定\"\nTip#1 1700000200 open \"translator\" = \"改成\\\\暫定名稱\"\n"
+        );
+        let parsed = parse_comments(&content);
+        let mut expected = comments.entries.clone();
+        expected.sort_by_key(|c| c.key.id.clone());
+        assert_eq!(parsed.entries, expected);
+    }
+}
@@ -1,13 +1,28 @@
 use crate::data::tooltip::SkillData;
 use regex::Regex;
+use std::fs;
 use std::iter::Peekable;
 
 pub const SOURCE_FILE_NAME: &str = "source.ini";
 pub const EXPORT_FILE_NAME: &str = "source_new.ini";
 pub const TRANSLATE_FILE_NAME: &str = "translation.ini";
+pub const GLOSSARY_FILE_NAME: &str = "glossary.ini";
+pub const QA_EXCEPTION_FILE_NAME: &str = "qa_exceptions.ini";
+pub const QA_SETTINGS_FILE_NAME: &str = "qa_settings.ini";
+pub const FINGERPRINT_FILE_NAME: &str = "translation_fingerprints.ini";
+pub const ARCHIVE_FILE_NAME: &str = "translation_archive.ini";
+pub const REVIEW_FLAG_FILE_NAME: &str = "translation_flags.ini";
+pub const STATUS_FILE_NAME: &str = "translation_status.ini";
+pub const COMMENT_FILE_NAME: &str = "translation_comments.ini";
+pub const RECOVERY_FILE_NAME: &str = "translation_recovery.ini";
+pub const RECOVERY_REVIEW_FLAG_FILE_NAME: &str = "translation_recovery_flags.ini";
+pub const RECOVERY_STATUS_FILE_NAME: &str = "translation_recovery_status.ini";
+pub const AUTOSAVE_INTERVAL_SECONDS: f64 = 60.0;
+pub const HISTORY_LIMIT: usize = 100;
 pub const PARSE_ID_REGEX: &str = r#"^\[([a-zA-Z0-9]{4}|[a-zA-Z0-9]{3}@)\]$"#;
 pub const EXPORT_ID_REGEX: &str = r#"^([a-zA-Z0-9]{4})|([a-zA-Z0-9]{3}@)$"#;
 pub const SINGLE_LINE_REGEX: &str = r#"^[A-Za-z]+\s*=\s*"(.*)"$"#;
+pub const CONTEXT_REGEX: &str = r#"^(_?[A-Za-z]+)\s*=\s*"(.*)"$"#;
 pub const SINGLE_LINE_ARRAY_REGEX: &str = r#"^"(.*)",$"#;
 pub const SINGLE_LINE_ARRAY_EXT_REGEX: &str = r#"^\d+\s*=\s*"(.*)",$"#;
 pub const MULTI_LINE_ARRAY_EXT_REGEX: &str = r#"^\d+\s*=\s*\[=\[$"#;
@@ -25,6 +40,53 @@ impl StringExt for String {
     }
 }
 
+// 設定檔中以雙引號包住的值，跳脫反斜線、雙引號與換行
+pub fn escape_value(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+pub fn unescape_value(text: &str) -> String {
+    let mut result = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+// 附屬檔案被其他編輯器存成非UTF-8時，無法解碼的字元以替代字元讀入，避免整個檔案被當成空的
+pub fn read_lossy(path: &str) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
 #[derive(Default, Debug, Hash, Eq, PartialEq, Clone, Ord, PartialOrd)]
 pub enum FieldType {
     #[default]
@@ -35,6 +97,13 @@ pub enum FieldType {
 }
 
 impl FieldType {
+    pub const ALL: [FieldType; 4] = [
+        FieldType::Researchtip,
+        FieldType::Researchubertip,
+        FieldType::Tip,
+        FieldType::Ubertip,
+    ];
+
     const RESEARCHTIP: &'static str = "Researchtip";
     const RESEARCHUBERTIP: &'static str = "Researchubertip";
     const TIP: &'static str = "Tip";
@@ -60,6 +129,83 @@ impl FieldType {
     }
 }
 
+// 不需翻譯但可作為比對依據的欄位
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Ord, PartialOrd)]
+pub enum ContextType {
+    Name,
+    Parent,
+    Hotkey,
+    Researchhotkey,
+}
+
+impl ContextType {
+    const NAME: &'static str = "Name";
+    const PARENT: &'static str = "_parent";
+    const HOTKEY: &'static str = "Hotkey";
+    const RESEARCHHOTKEY: &'static str = "Researchhotkey";
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<ContextType> {
+        match s {
+            Self::NAME => Some(ContextType::Name),
+            Self::PARENT => Some(ContextType::Parent),
+            Self::HOTKEY => Some(ContextType::Hotkey),
+            Self::RESEARCHHOTKEY => Some(ContextType::Researchhotkey),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ContextType::Name => Self::NAME,
+            ContextType::Parent => Self::PARENT,
+            ContextType::Hotkey => Self::HOTKEY,
+            ContextType::Researchhotkey => Self::RESEARCHHOTKEY,
+        }
+    }
+}
+
+// 物件編輯器的資料表，依照魔獸ID第一個字元的慣例判斷
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ObjectTable {
+    Ability,
+    Buff,
+    Item,
+    Upgrade,
+    Unit,
+}
+
+impl ObjectTable {
+    pub const ALL: [ObjectTable; 5] = [
+        ObjectTable::Ability,
+        ObjectTable::Buff,
+        ObjectTable::Item,
+        ObjectTable::Upgrade,
+        ObjectTable::Unit,
+    ];
+
+    // 只看大寫的第一個字元，內建物品的ID是小寫(例如bspd、rat6、ankh)，不能當成技能或科技
+    pub fn from_id(id: &str) -> ObjectTable {
+        match id.chars().next() {
+            Some('A') => ObjectTable::Ability,
+            Some('B') => ObjectTable::Buff,
+            Some('I') => ObjectTable::Item,
+            Some('R') => ObjectTable::Upgrade,
+            _ => ObjectTable::Unit,
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ObjectTable::Ability => "技能",
+            ObjectTable::Buff => "魔法效果",
+            ObjectTable::Item => "物品",
+            ObjectTable::Upgrade => "科技",
+            ObjectTable::Unit => "單位",
+        }
+    }
+}
+
 #[derive(Default, Debug, PartialEq, Clone)]
 pub enum TextType {
     #[default]
@@ -94,6 +240,13 @@ pub fn get_field_type(line: &str) -> Option<FieldType> {
     FieldType::from_str(field_name)
 }
 
+pub fn get_context(line: &str) -> Option<(ContextType, String)> {
+    let pattern = Regex::new(CONTEXT_REGEX).unwrap();
+    let caps = pattern.captures(line)?;
+    let context_type = ContextType::from_str(caps.get(1)?.as_str())?;
+    Some((context_type, caps.get(2)?.as_str().to_string()))
+}
+
 pub fn is_available_skill_data(data: &SkillData) -> bool {
     !data.id.is_empty()
         && [
@@ -232,6 +385,28 @@ mod tests {
         assert_eq!(get_parse_type_from_single("123 = [=["), None);
     }
 
+    #[test]
+    fn test_get_context() {
+        assert_eq!(
+            get_context(r#"_parent = "ANcl""#),
+            Some((ContextType::Parent, "ANcl".to_string()))
+        );
+        assert_eq!(
+            get_context(r#"Name = "(Az03) HassanKing - (E)""#),
+            Some((ContextType::Name, "(Az03) HassanKing - (E)".to_string()))
+        );
+        assert_eq!(
+            get_context(r#"Hotkey = "E""#),
+            Some((ContextType::Hotkey, "E".to_string()))
+        );
+        assert_eq!(
+            get_context(r#"Researchhotkey = "Q""#),
+            Some((ContextType::Researchhotkey, "Q".to_string()))
+        );
+        assert_eq!(get_context(r#"Tip = "E""#), None);
+        assert_eq!(get_context("Buttonpos_2 = 2"), None);
+    }
+
     #[test]
     fn test_get_parse_type_from_multi() {
         assert_eq!(
@@ -260,4 +435,19 @@ mod tests {
             Some(TextType::MultiLineArrayExt)
         );
     }
+
+    #[test]
+    fn test_object_table_from_id() {
+        assert_eq!(ObjectTable::from_id("A000"), ObjectTable::Ability);
+        assert_eq!(ObjectTable::from_id("AHbz"), ObjectTable::Ability);
+        assert_eq!(ObjectTable::from_id("B001"), ObjectTable::Buff);
+        assert_eq!(ObjectTable::from_id("I002"), ObjectTable::Item);
+        assert_eq!(ObjectTable::from_id("R003"), ObjectTable::Upgrade);
+        assert_eq!(ObjectTable::from_id("h000"), ObjectTable::Unit);
+
+        // 小寫開頭的內建ID不會被歸到技能、魔法效果或科技
+        for id in ["bspd", "rat6", "ankh", "ckng"] {
+            assert_eq!(ObjectTable::from_id(id), ObjectTable::Unit, "{}", id);
+        }
+    }
 }
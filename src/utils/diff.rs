@@ -0,0 +1,195 @@
+#[derive(Debug, PartialEq, Clone)]
+pub enum TokenKind {
+    Number,
+    Word,
+    Whitespace,
+    Symbol,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    // 在原字串中的位元組位置
+    pub start: usize,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DiffOp {
+    Equal(String),
+    Delete(String),
+    Insert(String),
+}
+
+pub fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let kind = if c.is_ascii_alphanumeric() {
+            // 英數混合(例如 cffffcc00、A011)視為單字，純數字才視為數值
+            let mut all_digits = c.is_ascii_digit();
+            while let Some(&(i, next)) = chars.peek() {
+                if next.is_ascii_alphanumeric() {
+                    all_digits &= next.is_ascii_digit();
+                } else if next == '.' && all_digits && is_digit_at(text, i + 1) {
+                    // 小數點
+                } else {
+                    break;
+                }
+                chars.next();
+            }
+            if all_digits {
+                TokenKind::Number
+            } else {
+                TokenKind::Word
+            }
+        } else if c.is_alphabetic() {
+            while chars
+                .peek()
+                .is_some_and(|&(_, next)| next.is_alphabetic() && !next.is_ascii())
+            {
+                chars.next();
+            }
+            TokenKind::Word
+        } else if c.is_whitespace() {
+            while chars.peek().is_some_and(|&(_, next)| next.is_whitespace()) {
+                chars.next();
+            }
+            TokenKind::Whitespace
+        } else {
+            TokenKind::Symbol
+        };
+
+        let end = chars.peek().map_or(text.len(), |&(i, _)| i);
+        tokens.push(Token {
+            kind,
+            text: &text[start..end],
+            start,
+        });
+    }
+
+    tokens
+}
+
+fn is_digit_at(text: &str, index: usize) -> bool {
+    text.as_bytes()
+        .get(index)
+        .is_some_and(|b| b.is_ascii_digit())
+}
+
+pub fn extract_numbers(text: &str) -> Vec<Token<'_>> {
+    tokenize(text)
+        .into_iter()
+        .filter(|token| token.kind == TokenKind::Number)
+        .collect()
+}
+
+// 以LCS比對兩段文字的token差異
+pub fn diff_tokens(old: &str, new: &str) -> Vec<DiffOp> {
+    let old_tokens: Vec<&str> = tokenize(old).iter().map(|t| t.text).collect();
+    let new_tokens: Vec<&str> = tokenize(new).iter().map(|t| t.text).collect();
+    let (n, m) = (old_tokens.len(), new_tokens.len());
+
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_tokens[i] == new_tokens[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut result: Vec<DiffOp> = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_tokens[i] == new_tokens[j] {
+            push_op(&mut result, DiffOp::Equal(old_tokens[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            push_op(&mut result, DiffOp::Delete(old_tokens[i].to_string()));
+            i += 1;
+        } else {
+            push_op(&mut result, DiffOp::Insert(new_tokens[j].to_string()));
+            j += 1;
+        }
+    }
+
+    result
+}
+
+// 合併相鄰的同類型片段
+fn push_op(result: &mut Vec<DiffOp>, op: DiffOp) {
+    match (result.last_mut(), op) {
+        (Some(DiffOp::Equal(last)), DiffOp::Equal(text))
+        | (Some(DiffOp::Delete(last)), DiffOp::Delete(text))
+        | (Some(DiffOp::Insert(last)), DiffOp::Insert(text)) => last.push_str(&text),
+        (_, op) => result.push(op),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("|cffffcc00레벨 1|r - 0.5초당 15, 50%");
+        let texts: Vec<&str> = tokens.iter().map(|t| t.text).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "|",
+                "cffffcc00",
+                "레벨",
+                " ",
+                "1",
+                "|",
+                "r",
+                " ",
+                "-",
+                " ",
+                "0.5",
+                "초당",
+                " ",
+                "15",
+                ",",
+                " ",
+                "50",
+                "%"
+            ]
+        );
+        assert_eq!(tokens[1].kind, TokenKind::Word);
+        assert_eq!(tokens[4].kind, TokenKind::Number);
+        assert_eq!(tokens[10].kind, TokenKind::Number);
+    }
+
+    #[test]
+    fn test_extract_numbers() {
+        let numbers: Vec<&str> = extract_numbers("정면 1600범위에 1000의 데미지, A011 1.")
+            .iter()
+            .map(|t| t.text)
+            .collect();
+        assert_eq!(numbers, vec!["1600", "1000", "1"]);
+    }
+
+    #[test]
+    fn test_diff_tokens() {
+        let result = diff_tokens("데미지 : 1000", "데미지 : 1350");
+        assert_eq!(
+            result,
+            vec![
+                DiffOp::Equal("데미지 : ".to_string()),
+                DiffOp::Delete("1000".to_string()),
+                DiffOp::Insert("1350".to_string()),
+            ]
+        );
+        assert_eq!(
+            diff_tokens("abc", "abc"),
+            vec![DiffOp::Equal("abc".to_string())]
+        );
+    }
+}
@@ -1,12 +1,15 @@
-use crate::data::tooltip::{SkillData, TooltipData};
+use crate::data::tooltip::{SegmentStatus, SkillData, TooltipData};
 use crate::utils::common::*;
+use crate::utils::status::filter_by_status;
 use regex::Regex;
 use std::collections::BTreeMap;
 use std::fs;
 use std::iter::Peekable;
 
-pub fn export_files(data: &TooltipData) -> Result<(), String> {
-    let output = output_files(&data.skill_manager.translation_skills)?;
+// 狀態低於下限的段落以原文匯出
+pub fn export_files(data: &TooltipData, min_status: &SegmentStatus) -> Result<(), String> {
+    let translation_skills = filter_by_status(&data.skill_manager, min_status);
+    let output = output_files(&translation_skills)?;
     fs::write(EXPORT_FILE_NAME, output).expect("Unable to write file");
     Ok(())
 }
@@ -0,0 +1,206 @@
+use crate::data::tooltip::{SegmentKey, SkillManager};
+use crate::utils::common::*;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+
+pub const FINGERPRINT_ID_REGEX: &str = r#"^\[(.+)\]$"#;
+pub const FINGERPRINT_REGEX: &str = r#"^([A-Za-z]+)#(\d+)(\.Source)?\s*=\s*"(.*)"$"#;
+pub const FINGERPRINT_KEEP_SOURCE_REGEX: &str = r#"^KeepSource\s*=\s*"(.*)"$"#;
+
+// 翻譯當時的原文，用來判斷原文是否在翻譯後被修改
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fingerprint {
+    pub hash: String,
+    pub source: Option<String>,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct SourceFingerprints {
+    // 是否同時保存原文副本，保存時才能顯示新舊原文的差異
+    pub keep_source: bool,
+    pub entries: BTreeMap<SegmentKey, Fingerprint>,
+}
+
+// FNV-1a，結果不會因為Rust版本不同而改變
+pub fn hash_source(source: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in source.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+impl SourceFingerprints {
+    pub fn record(&mut self, key: &SegmentKey, source: &str) {
+        self.entries.insert(
+            key.clone(),
+            Fingerprint {
+                hash: hash_source(source),
+                source: self.keep_source.then(|| source.to_string()),
+            },
+        );
+    }
+
+    pub fn is_stale(&self, key: &SegmentKey, source: &str) -> bool {
+        self.entries
+            .get(key)
+            .is_some_and(|fingerprint| fingerprint.hash != hash_source(source))
+    }
+
+    // 翻譯時的原文，沒有保存副本時回傳None
+    pub fn old_source(&self, key: &SegmentKey) -> Option<&str> {
+        self.entries.get(key)?.source.as_deref()
+    }
+
+    pub fn stale_keys(&self, manager: &SkillManager) -> Vec<SegmentKey> {
+        self.entries
+            .keys()
+            .filter(|key| {
+                manager
+                    .get_source(key)
+                    .is_some_and(|source| self.is_stale(key, source))
+            })
+            .cloned()
+            .collect()
+    }
+
+    // 已翻譯但還沒有記錄的段落以目前的原文為準，已記錄的段落不更新才能保留過時標記
+    pub fn fill_missing(&mut self, manager: &SkillManager) {
+        for key in manager.segment_keys() {
+            if self.entries.contains_key(&key) || !manager.is_translated(&key) {
+                continue;
+            }
+            if let Some(source) = manager.get_source(&key) {
+                self.record(&key, source);
+            }
+        }
+    }
+}
+
+pub fn load_fingerprints(path: &str) -> Result<SourceFingerprints, String> {
+    let content = read_lossy(path)?;
+    Ok(parse_fingerprints(&content))
+}
+
+pub fn save_fingerprints(path: &str, fingerprints: &SourceFingerprints) -> Result<(), String> {
+    fs::write(path, output_fingerprints(fingerprints)).map_err(|e| e.to_string())
+}
+
+pub fn parse_fingerprints(content: &str) -> SourceFingerprints {
+    let id_pattern = Regex::new(FINGERPRINT_ID_REGEX).unwrap();
+    let pattern = Regex::new(FINGERPRINT_REGEX).unwrap();
+    let keep_source_pattern = Regex::new(FINGERPRINT_KEEP_SOURCE_REGEX).unwrap();
+
+    let mut fingerprints = SourceFingerprints::default();
+    let mut current_id = None;
+    for line in content.lines() {
+        if let Some(caps) = keep_source_pattern.captures(line) {
+            fingerprints.keep_source = &caps[1] == "true";
+            continue;
+        }
+        if let Some(caps) = id_pattern.captures(line) {
+            current_id = Some(caps[1].to_string());
+            continue;
+        }
+
+        let (Some(id), Some(caps)) = (&current_id, pattern.captures(line)) else {
+            continue;
+        };
+        let Some(field_type) = FieldType::from_str(&caps[1]) else {
+            continue;
+        };
+        let Ok(level) = caps[2].parse::<usize>() else {
+            continue;
+        };
+        let key = SegmentKey::new(id, field_type, level.saturating_sub(1));
+        let value = unescape_value(&caps[4]);
+        let entry = fingerprints
+            .entries
+            .entry(key)
+            .or_insert_with(|| Fingerprint {
+                hash: String::new(),
+                source: None,
+            });
+        match caps.get(3) {
+            Some(_) => entry.source = Some(value),
+            None => entry.hash = value,
+        }
+    }
+
+    fingerprints
+}
+
+pub fn output_fingerprints(fingerprints: &SourceFingerprints) -> String {
+    let mut output = String::new();
+    output.push_line(&format!("KeepSource = \"{}\"", fingerprints.keep_source));
+
+    let mut current_id: Option<&str> = None;
+    for (key, fingerprint) in &fingerprints.entries {
+        if current_id != Some(key.id.as_str()) {
+            output.push(NEWLINE_SYMBOL);
+            output.push_line(&format!("[{}]", key.id));
+            current_id = Some(key.id.as_str());
+        }
+        let name = format!("{}#{}", key.field_type.to_str(), key.level + 1);
+        output.push_line(&format!("{} = \"{}\"", name, fingerprint.hash));
+        if let Some(source) = &fingerprint.source {
+            output.push_line(&format!("{}.Source = \"{}\"", name, escape_value(source)));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{manager, tip_skill};
+
+    #[test]
+    fn test_parse_and_output_fingerprints() {
+        let mut fingerprints = SourceFingerprints {
+            keep_source: true,
+            ..Default::default()
+        };
+        let key = SegmentKey::new("A000", FieldType::Ubertip, 1);
+        fingerprints.record(&key, "레벨 2\n\"데미지\" 150");
+        fingerprints.keep_source = false;
+        fingerprints.record(&SegmentKey::new("A001", FieldType::Tip, 0), "마나");
+        fingerprints.keep_source = true;
+
+        let content = output_fingerprints(&fingerprints);
+        assert_eq!(
+            content,
+            format!(
+                "KeepSource = \"true\"\n\n[A000]\nUbertip#2 = \"{}\"\nUbertip#2.Source = \"레벨 2\\n\\\"데미지\\\" 150\"\n\n[A001]\nTip#1 = \"{}\"\n",
+                hash_source("레벨 2\n\"데미지\" 150"),
+                hash_source("마나")
+            )
+        );
+
+        let parsed = parse_fingerprints(&content);
+        assert!(parsed.keep_source);
+        assert_eq!(parsed.entries, fingerprints.entries);
+        assert_eq!(parsed.old_source(&key), Some("레벨 2\n\"데미지\" 150"));
+    }
+
+    #[test]
+    fn test_stale_keys() {
+        let mut manager = manager(
+            vec![tip_skill("A000", &["레벨 1", "레벨 2"])],
+            vec![tip_skill("A000", &["等級 1", "等級 2"])],
+        );
+
+        let mut fingerprints = SourceFingerprints::default();
+        fingerprints.fill_missing(&manager);
+        assert_eq!(fingerprints.entries.len(), 2);
+        assert!(fingerprints.stale_keys(&manager).is_empty());
+
+        manager.skills.get_mut("A000").unwrap().tip[1] = "레벨 2 (강화)".to_string();
+        fingerprints.fill_missing(&manager);
+        let stale = fingerprints.stale_keys(&manager);
+        assert_eq!(stale, vec![SegmentKey::new("A000", FieldType::Tip, 1)]);
+        assert_eq!(fingerprints.old_source(&stale[0]), None);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::data::tooltip::{ReviewFlag, SegmentKey, SkillManager};
+use crate::utils::common::FieldType;
+use crate::utils::diff::{extract_numbers, tokenize, Token, TokenKind};
+use crate::utils::memory::adapt_numbers;
+use crate::utils::status::set_review_flag;
+
+// 除了數值以外，兩段文字的token必須完全相同
+fn same_structure(first: &[Token], other: &[Token]) -> bool {
+    first.len() == other.len()
+        && first.iter().zip(other.iter()).all(|(a, b)| match a.kind {
+            TokenKind::Number => b.kind == TokenKind::Number,
+            _ => a.kind == b.kind && a.text == b.text,
+        })
+}
+
+// 回傳第1級中會隨等級變化的數值
+fn changing_numbers(levels: &[String]) -> Result<Vec<&str>, String> {
+    let first = tokenize(&levels[0]);
+    for (level, text) in levels.iter().enumerate().skip(1) {
+        if !same_structure(&first, &tokenize(text)) {
+            return Err(format!(
+                "第{}級的原文與第1級除了數值以外還有其他差異",
+                level + 1
+            ));
+        }
+    }
+
+    let numbers: Vec<Vec<Token>> = levels.iter().map(|text| extract_numbers(text)).collect();
+    Ok(numbers[0]
+        .iter()
+        .enumerate()
+        .filter(|(index, number)| {
+            numbers[1..]
+                .iter()
+                .any(|other| other[*index].text != number.text)
+        })
+        .map(|(_, number)| number.text)
+        .collect())
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct GeneratedLevels {
+    pub generated: Vec<SegmentKey>,
+    // 已經有翻譯而沒有覆蓋的等級
+    pub skipped: Vec<SegmentKey>,
+}
+
+// 以翻譯好的第1級產生第2級之後的翻譯，只替換原文中隨等級變化的數值
+pub fn generate_levels(
+    manager: &mut SkillManager,
+    id: &str,
+    field_type: &FieldType,
+) -> Result<GeneratedLevels, String> {
+    let first_key = SegmentKey::new(id, field_type.clone(), 0);
+    let levels = manager
+        .skills
+        .get(id)
+        .map(|skill| skill.get_field(field_type).clone())
+        .unwrap_or_default();
+    if levels.len() < 2 {
+        return Err("原文只有一個等級".to_string());
+    }
+    if !manager.is_translated(&first_key) {
+        return Err("第1級尚未翻譯".to_string());
+    }
+    let translation = manager
+        .get_translation(&first_key)
+        .cloned()
+        .unwrap_or_default();
+
+    let changing = changing_numbers(&levels)?;
+    let translated_numbers = extract_numbers(&translation);
+    for number in &changing {
+        let source_count = changing.iter().filter(|n| *n == number).count();
+        let count = translated_numbers
+            .iter()
+            .filter(|n| n.text == *number)
+            .count();
+        if count < source_count {
+            return Err(format!(
+                "第1級的翻譯中找不到會隨等級變化的數值「{}」",
+                number
+            ));
+        }
+    }
+
+    let mut result = GeneratedLevels::default();
+    for (level, source) in levels.iter().enumerate().skip(1) {
+        let key = SegmentKey::new(id, field_type.clone(), level);
+        if manager.is_translated(&key) {
+            result.skipped.push(key);
+            continue;
+        }
+        let value = adapt_numbers(&levels[0], &translation, source);
+        if manager.set_translation(&key, value) {
+            set_review_flag(manager, &key, ReviewFlag::Generated);
+            result.generated.push(key);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{self, tip_skill};
+
+    fn manager(source: &[&str], translation: &[&str]) -> SkillManager {
+        test_util::manager(
+            vec![tip_skill("A000", source)],
+            vec![tip_skill("A000", translation)],
+        )
+    }
+
+    #[test]
+    fn test_generate_levels() {
+        let mut manager = manager(
+            &[
+                "레벨 1 - 3초 동안 100 데미지",
+                "레벨 2 - 3초 동안 150 데미지",
+                "레벨 3 - 3초 동안 200 데미지",
+            ],
+            &["等級 1 - 3秒內造成100傷害"],
+        );
+        let result = generate_levels(&mut manager, "A000", &FieldType::Tip).unwrap();
+        let generated = result.generated;
+        assert_eq!(generated.len(), 2);
+        assert!(result.skipped.is_empty());
+        assert_eq!(
+            manager.translation_skills["A000"].tip,
+            vec![
+                "等級 1 - 3秒內造成100傷害",
+                "等級 2 - 3秒內造成150傷害",
+                "等級 3 - 3秒內造成200傷害",
+            ]
+        );
+        assert_eq!(
+            manager.review_flags.get(&generated[1]),
+            Some(&ReviewFlag::Generated)
+        );
+    }
+
+    #[test]
+    fn test_generate_levels_keeps_translated_levels() {
+        let mut manager = manager(
+            &[
+                "레벨 1 - 100 데미지",
+                "레벨 2 - 150 데미지",
+                "레벨 3 - 200 데미지",
+            ],
+            &["等級 1 - 100傷害", "第二級 - 造成150點傷害"],
+        );
+        let result = generate_levels(&mut manager, "A000", &FieldType::Tip).unwrap();
+        let second = SegmentKey::new("A000", FieldType::Tip, 1);
+        assert_eq!(result.skipped, vec![second.clone()]);
+        assert_eq!(
+            result.generated,
+            vec![SegmentKey::new("A000", FieldType::Tip, 2)]
+        );
+        assert_eq!(
+            manager.translation_skills["A000"].tip,
+            vec![
+                "等級 1 - 100傷害",
+                "第二級 - 造成150點傷害",
+                "等級 3 - 200傷害"
+            ]
+        );
+        assert!(!manager.review_flags.contains_key(&second));
+    }
+
+    #[test]
+    fn test_generate_levels_refused() {
+        let mut refused = manager(
+            &["레벨 1 - 100 데미지", "레벨 2 - 150 데미지, 기절"],
+            &["等級 1 - 100傷害"],
+        );
+        assert_eq!(
+            generate_levels(&mut refused, "A000", &FieldType::Tip),
+            Err("第2級的原文與第1級除了數值以外還有其他差異".to_string())
+        );
+
+        let mut refused = manager(
+            &["레벨 1 - 100 데미지", "레벨 2 - 150 데미지"],
+            &["等級一 - 100傷害"],
+        );
+        assert_eq!(
+            generate_levels(&mut refused, "A000", &FieldType::Tip),
+            Err("第1級的翻譯中找不到會隨等級變化的數值「1」".to_string())
+        );
+
+        let mut refused = manager(&["레벨 1", "레벨 2"], &["레벨 1"]);
+        assert_eq!(
+            generate_levels(&mut refused, "A000", &FieldType::Tip),
+            Err("第1級尚未翻譯".to_string())
+        );
+    }
+}
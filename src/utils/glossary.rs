@@ -0,0 +1,206 @@
+use crate::utils::common::*;
+use regex::Regex;
+use std::fs;
+use std::ops::Range;
+
+pub const GLOSSARY_TERM_REGEX: &str = r#"^\[(.+)\]$"#;
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct GlossaryEntry {
+    pub term: String,
+    pub target: String,
+    pub notes: String,
+    pub forbidden: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct Glossary {
+    pub entries: Vec<GlossaryEntry>,
+}
+
+impl Glossary {
+    // 找出文字中出現的術語，較長的術語優先且不重疊
+    pub fn find_terms(&self, text: &str) -> Vec<(Range<usize>, &GlossaryEntry)> {
+        let mut entries: Vec<&GlossaryEntry> =
+            self.entries.iter().filter(|e| !e.term.is_empty()).collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.term.len()));
+
+        let mut result: Vec<(Range<usize>, &GlossaryEntry)> = vec![];
+        for entry in entries {
+            for (start, _) in text.match_indices(entry.term.as_str()) {
+                let range = start..start + entry.term.len();
+                if result
+                    .iter()
+                    .any(|(r, _)| r.start < range.end && range.start < r.end)
+                {
+                    continue;
+                }
+                result.push((range, entry));
+            }
+        }
+        result.sort_by_key(|(range, _)| range.start);
+        result
+    }
+}
+
+pub fn load_glossary(path: &str) -> Result<Glossary, String> {
+    let content = read_lossy(path)?;
+    Ok(parse_glossary(&content))
+}
+
+pub fn save_glossary(path: &str, glossary: &Glossary) -> Result<(), String> {
+    fs::write(path, output_glossary(glossary)).map_err(|e| e.to_string())
+}
+
+pub fn parse_glossary(content: &str) -> Glossary {
+    let term_pattern = Regex::new(GLOSSARY_TERM_REGEX).unwrap();
+    let value_pattern = Regex::new(SINGLE_LINE_REGEX).unwrap();
+    let array_pattern = Regex::new(SINGLE_LINE_ARRAY_REGEX).unwrap();
+
+    let mut glossary = Glossary::default();
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        if let Some(caps) = term_pattern.captures(line) {
+            glossary.entries.push(GlossaryEntry {
+                term: unescape_value(&caps[1]),
+                ..Default::default()
+            });
+            continue;
+        }
+
+        let Some(entry) = glossary.entries.last_mut() else {
+            continue;
+        };
+        let Some((key, _)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim() {
+            "Target" | "Notes" => {
+                let value = value_pattern
+                    .captures(line)
+                    .map(|caps| unescape_value(&caps[1]))
+                    .unwrap_or_default();
+                if key.trim() == "Target" {
+                    entry.target = value;
+                } else {
+                    entry.notes = value;
+                }
+            }
+            "Forbidden" => {
+                entry.forbidden = lines
+                    .by_ref()
+                    .take_while(|line| *line != "}")
+                    .filter_map(|line| array_pattern.captures(line).map(|c| unescape_value(&c[1])))
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+
+    glossary
+}
+
+pub fn output_glossary(glossary: &Glossary) -> String {
+    let mut output = String::new();
+    for entry in &glossary.entries {
+        output.push_line(&format!("[{}]", escape_value(&entry.term)));
+        output.push_line(&format!("Target = \"{}\"", escape_value(&entry.target)));
+        if !entry.notes.is_empty() {
+            output.push_line(&format!("Notes = \"{}\"", escape_value(&entry.notes)));
+        }
+        let forbidden: Vec<&str> = entry
+            .forbidden
+            .iter()
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .collect();
+        if !forbidden.is_empty() {
+            output.push_line("Forbidden = {");
+            for variant in forbidden {
+                output.push_line(&format!("\"{}\",", escape_value(variant)));
+            }
+            output.push_line("}");
+        }
+        output.push(NEWLINE_SYMBOL);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glossary() -> Glossary {
+        Glossary {
+            entries: vec![
+                GlossaryEntry {
+                    term: "올스텟".to_string(),
+                    target: "全屬性".to_string(),
+                    notes: "力量/敏捷/智力".to_string(),
+                    forbidden: vec!["全能力".to_string(), "全數值".to_string()],
+                },
+                GlossaryEntry {
+                    term: "쿨다운".to_string(),
+                    target: "冷卻".to_string(),
+                    ..Default::default()
+                },
+                GlossaryEntry {
+                    term: "절대쿨다운".to_string(),
+                    target: "絕對冷卻".to_string(),
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_parse_and_output_glossary() {
+        let content = output_glossary(&glossary());
+        assert!(content.starts_with(
+            r#"[올스텟]
+Target = "全屬性"
+Notes = "力量/敏捷/智力"
+Forbidden = {
+"全能力",
+"全數值",
+}
+
+[쿨다운]
+Target = "冷卻"
+"#
+        ));
+        assert_eq!(parse_glossary(&content).entries, glossary().entries);
+    }
+
+    #[test]
+    fn test_output_glossary_escapes_values() {
+        let glossary = Glossary {
+            entries: vec![GlossaryEntry {
+                term: "\"마나\"".to_string(),
+                target: "「魔力」\"法力\"".to_string(),
+                notes: "第一行\n第二行 C:\\path".to_string(),
+                forbidden: vec!["\"魔法\"".to_string()],
+            }],
+        };
+        let content = output_glossary(&glossary);
+        assert!(content.contains("Target = \"「魔力」\\\"法力\\\"\"\n"));
+        assert!(content.contains("Notes = \"第一行\\n第二行 C:\\\\path\"\n"));
+        assert_eq!(content.lines().count(), 7);
+        assert_eq!(parse_glossary(&content).entries, glossary.entries);
+    }
+
+    #[test]
+    fn test_find_terms() {
+        let glossary = glossary();
+        let text = "올스텟 3 증가, 절대쿨다운 : 120초, 쿨다운 : 45초";
+        let found: Vec<&str> = glossary
+            .find_terms(text)
+            .iter()
+            .map(|(range, entry)| {
+                assert_eq!(&text[range.clone()], entry.term);
+                entry.term.as_str()
+            })
+            .collect();
+        assert_eq!(found, vec!["올스텟", "절대쿨다운", "쿨다운"]);
+    }
+}
@@ -0,0 +1,457 @@
+use crate::data::tooltip::{ReviewFlag, SegmentKey, SegmentStatus, SkillData, SkillManager};
+use crate::utils::common::HISTORY_LIMIT;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+
+// 批次操作(取代、匯入等)會影響多個物件，在分開記錄時放在共用的記錄中
+const SHARED_STACK: &str = "";
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HistoryScope {
+    #[default]
+    Global,
+    PerObject,
+}
+
+// 單一物件的翻譯與各段落的標記、審校狀態，翻譯為None代表沒有翻譯資料
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectState {
+    pub translation: Option<SkillData>,
+    pub review_flags: BTreeMap<SegmentKey, ReviewFlag>,
+    pub statuses: BTreeMap<SegmentKey, SegmentStatus>,
+}
+
+impl ObjectState {
+    fn take(manager: &SkillManager, id: &str) -> Self {
+        Self {
+            translation: manager.translation_skills.get(id).cloned(),
+            review_flags: manager
+                .review_flags
+                .iter()
+                .filter(|(key, _)| key.id == id)
+                .map(|(key, flag)| (key.clone(), flag.clone()))
+                .collect(),
+            statuses: manager
+                .statuses
+                .iter()
+                .filter(|(key, _)| key.id == id)
+                .map(|(key, status)| (key.clone(), status.clone()))
+                .collect(),
+        }
+    }
+}
+
+// 單一物件在操作前後的狀態
+#[derive(Debug, Clone)]
+pub struct SkillChange {
+    pub id: String,
+    pub before: ObjectState,
+    pub after: ObjectState,
+}
+
+#[derive(Debug, Clone)]
+pub struct Command {
+    pub label: String,
+    pub changes: Vec<SkillChange>,
+    sequence: usize,
+}
+
+impl Command {
+    fn stack_key(&self) -> &str {
+        match self.changes.as_slice() {
+            [change] => &change.id,
+            _ => SHARED_STACK,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct HistoryStack {
+    undo: Vec<Command>,
+    redo: Vec<Command>,
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    scope: HistoryScope,
+    stacks: BTreeMap<String, HistoryStack>,
+    sequence: usize,
+}
+
+impl History {
+    pub fn scope(&self) -> HistoryScope {
+        self.scope
+    }
+
+    pub fn clear(&mut self) {
+        self.stacks.clear();
+    }
+
+    // 切換記錄方式時清除所有記錄
+    pub fn set_scope(&mut self, scope: HistoryScope) {
+        if self.scope != scope {
+            self.scope = scope;
+            self.stacks.clear();
+        }
+    }
+
+    pub fn record(&mut self, mut command: Command) {
+        self.sequence += 1;
+        command.sequence = self.sequence;
+        let key = match self.scope {
+            HistoryScope::Global => SHARED_STACK.to_string(),
+            HistoryScope::PerObject => command.stack_key().to_string(),
+        };
+
+        // 新的操作之後不能再重做被覆蓋的操作，批次操作會影響所有物件
+        for (stack_key, stack) in self.stacks.iter_mut() {
+            if key == SHARED_STACK || stack_key == &key || stack_key == SHARED_STACK {
+                stack.redo.clear();
+            }
+        }
+        let stack = self.stacks.entry(key).or_default();
+        stack.undo.push(command);
+        if stack.undo.len() > HISTORY_LIMIT {
+            stack.undo.remove(0);
+        }
+    }
+
+    // 目前可使用的記錄，分開記錄時包含目前物件與共用的記錄
+    fn stack_keys<'a>(&self, current_id: &'a str) -> Vec<&'a str> {
+        match self.scope {
+            HistoryScope::Global => vec![SHARED_STACK],
+            HistoryScope::PerObject => vec![current_id, SHARED_STACK],
+        }
+    }
+
+    // 可復原的操作，最新的在前面
+    pub fn undo_entries(&self, current_id: &str) -> Vec<&Command> {
+        let mut entries: Vec<&Command> = self
+            .stack_keys(current_id)
+            .into_iter()
+            .filter_map(|key| self.stacks.get(key))
+            .flat_map(|stack| stack.undo.iter())
+            .collect();
+        entries.sort_by_key(|command| Reverse(command.sequence));
+        entries
+    }
+
+    // 可重做的操作，下一個要重做的在前面
+    pub fn redo_entries(&self, current_id: &str) -> Vec<&Command> {
+        let mut entries: Vec<&Command> = self
+            .stack_keys(current_id)
+            .into_iter()
+            .filter_map(|key| self.stacks.get(key))
+            .flat_map(|stack| stack.redo.iter())
+            .collect();
+        entries.sort_by_key(|command| command.sequence);
+        entries
+    }
+
+    // 共用的記錄涉及的物件有較新的操作時不能復原，否則會蓋掉該物件較新的修改
+    fn pop_undo(&mut self, current_id: &str) -> Result<Command, String> {
+        let (key, sequence) = self
+            .stack_keys(current_id)
+            .into_iter()
+            .filter_map(|key| Some((key, self.stacks.get(key)?.undo.last()?.sequence)))
+            .max_by_key(|(_, sequence)| *sequence)
+            .ok_or("沒有可以復原的操作")?;
+        let stack = self.stacks.get(key).unwrap();
+        if key == SHARED_STACK {
+            let command = stack.undo.last().unwrap();
+            for change in &command.changes {
+                let newer = self.stacks.get(&change.id).and_then(|s| s.undo.last());
+                if newer.is_some_and(|newer| newer.sequence > sequence) {
+                    return Err(format!(
+                        "{}有較新的操作，請先切換到該物件復原後再復原「{}」",
+                        change.id, command.label
+                    ));
+                }
+            }
+        }
+        Ok(self.stacks.get_mut(key).unwrap().undo.pop().unwrap())
+    }
+
+    // 共用的記錄涉及的物件有較早的操作尚未重做時不能重做
+    fn pop_redo(&mut self, current_id: &str) -> Result<Command, String> {
+        let (key, sequence) = self
+            .stack_keys(current_id)
+            .into_iter()
+            .filter_map(|key| Some((key, self.stacks.get(key)?.redo.last()?.sequence)))
+            .min_by_key(|(_, sequence)| *sequence)
+            .ok_or("沒有可以重做的操作")?;
+        let stack = self.stacks.get(key).unwrap();
+        if key == SHARED_STACK {
+            let command = stack.redo.last().unwrap();
+            for change in &command.changes {
+                let older = self.stacks.get(&change.id).and_then(|s| s.redo.last());
+                if older.is_some_and(|older| older.sequence < sequence) {
+                    return Err(format!(
+                        "{}有較早的操作尚未重做，請先切換到該物件重做後再重做「{}」",
+                        change.id, command.label
+                    ));
+                }
+            }
+        }
+        Ok(self.stacks.get_mut(key).unwrap().redo.pop().unwrap())
+    }
+
+    fn push_undone(&mut self, command: Command) {
+        let key = self.stack_key_of(&command);
+        self.stacks.entry(key).or_default().redo.push(command);
+    }
+
+    fn push_redone(&mut self, command: Command) {
+        let key = self.stack_key_of(&command);
+        self.stacks.entry(key).or_default().undo.push(command);
+    }
+
+    fn stack_key_of(&self, command: &Command) -> String {
+        match self.scope {
+            HistoryScope::Global => SHARED_STACK.to_string(),
+            HistoryScope::PerObject => command.stack_key().to_string(),
+        }
+    }
+}
+
+// 操作前的翻譯內容，操作完成後與目前內容比較產生記錄
+pub struct Snapshot {
+    before: Vec<(String, ObjectState)>,
+}
+
+impl Snapshot {
+    pub fn take<'a>(manager: &SkillManager, ids: impl IntoIterator<Item = &'a str>) -> Self {
+        let ids: BTreeSet<&str> = ids.into_iter().collect();
+        Self {
+            before: ids
+                .into_iter()
+                .map(|id| (id.to_string(), ObjectState::take(manager, id)))
+                .collect(),
+        }
+    }
+
+    // 包含所有原文與翻譯中的物件
+    pub fn take_all(manager: &SkillManager) -> Self {
+        let ids: Vec<String> = manager
+            .skills
+            .keys()
+            .chain(manager.translation_skills.keys())
+            .cloned()
+            .collect();
+        Self::take(manager, ids.iter().map(|id| id.as_str()))
+    }
+
+    // 翻譯文字是否與操作前不同，不比較標記與審校狀態
+    pub fn translation_changed(&self, manager: &SkillManager) -> bool {
+        self.before
+            .iter()
+            .any(|(id, before)| before.translation.as_ref() != manager.translation_skills.get(id))
+    }
+
+    // 有內容改變時寫入記錄並回傳true
+    pub fn record(self, manager: &mut SkillManager, label: impl Into<String>) -> bool {
+        let changes: Vec<SkillChange> = self
+            .before
+            .into_iter()
+            .filter_map(|(id, before)| {
+                let after = ObjectState::take(manager, &id);
+                (before != after).then_some(SkillChange { id, before, after })
+            })
+            .collect();
+        if changes.is_empty() {
+            return false;
+        }
+        manager.history.record(Command {
+            label: label.into(),
+            changes,
+            sequence: 0,
+        });
+        true
+    }
+}
+
+fn restore(manager: &mut SkillManager, id: &str, state: ObjectState) {
+    match state.translation {
+        Some(data) => {
+            manager.translation_skills.insert(id.to_string(), data);
+        }
+        None => {
+            manager.translation_skills.remove(id);
+        }
+    }
+    manager.review_flags.retain(|key, _| key.id != id);
+    manager.review_flags.extend(state.review_flags);
+    manager.statuses.retain(|key, _| key.id != id);
+    manager.statuses.extend(state.statuses);
+}
+
+// 回傳被復原的操作名稱，無法復原時回傳原因
+pub fn undo(manager: &mut SkillManager, current_id: &str) -> Result<String, String> {
+    let command = manager.history.pop_undo(current_id)?;
+    for change in &command.changes {
+        restore(manager, &change.id, change.before.clone());
+    }
+    let label = command.label.clone();
+    manager.history.push_undone(command);
+    Ok(label)
+}
+
+pub fn redo(manager: &mut SkillManager, current_id: &str) -> Result<String, String> {
+    let command = manager.history.pop_redo(current_id)?;
+    for change in &command.changes {
+        restore(manager, &change.id, change.after.clone());
+    }
+    let label = command.label.clone();
+    manager.history.push_redone(command);
+    Ok(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{self, skill};
+    use crate::utils::common::{FieldType, TextType};
+    use crate::utils::propagate::propagate_segment;
+
+    fn manager() -> SkillManager {
+        let skills = ["A000", "A001"]
+            .into_iter()
+            .map(|id| skill(id, TextType::SingleLine, &[(FieldType::Tip, &["마나"])]))
+            .collect();
+        test_util::manager(skills, vec![])
+    }
+
+    fn edit(manager: &mut SkillManager, id: &str, value: &str) {
+        let snapshot = Snapshot::take(manager, [id]);
+        manager.set_translation(&SegmentKey::new(id, FieldType::Tip, 0), value.to_string());
+        snapshot.record(manager, format!("編輯{}", id));
+    }
+
+    fn tip(manager: &SkillManager, id: &str) -> Option<String> {
+        Some(manager.translation_skills.get(id)?.tip[0].clone())
+    }
+
+    #[test]
+    fn test_global_history() {
+        let mut manager = manager();
+        edit(&mut manager, "A000", "魔力");
+        edit(&mut manager, "A001", "法力");
+        edit(&mut manager, "A000", "魔法");
+        let snapshot = Snapshot::take(&manager, ["A000"]);
+        assert!(!snapshot.record(&mut manager, "沒有改變"));
+
+        assert_eq!(undo(&mut manager, "A000"), Ok("編輯A000".to_string()));
+        assert_eq!(tip(&manager, "A000"), Some("魔力".to_string()));
+        assert_eq!(undo(&mut manager, "A000"), Ok("編輯A001".to_string()));
+        assert_eq!(tip(&manager, "A001"), None);
+        assert_eq!(manager.history.redo_entries("A000").len(), 2);
+
+        assert_eq!(redo(&mut manager, "A000"), Ok("編輯A001".to_string()));
+        assert_eq!(tip(&manager, "A001"), Some("法力".to_string()));
+
+        // 新的操作會清除重做記錄
+        edit(&mut manager, "A001", "魔能");
+        assert!(redo(&mut manager, "A000").is_err());
+        assert_eq!(manager.history.undo_entries("A000").len(), 3);
+    }
+
+    #[test]
+    fn test_per_object_history() {
+        let mut manager = manager();
+        manager.history.set_scope(HistoryScope::PerObject);
+        edit(&mut manager, "A000", "魔力");
+        edit(&mut manager, "A001", "法力");
+
+        let snapshot = Snapshot::take_all(&manager);
+        for (id, value) in [("A000", "魔法"), ("A001", "魔能")] {
+            let key = SegmentKey::new(id, FieldType::Tip, 0);
+            manager.set_translation(&key, value.to_string());
+        }
+        snapshot.record(&mut manager, "取代");
+
+        // 批次操作在任何物件都可以復原
+        assert_eq!(undo(&mut manager, "A000"), Ok("取代".to_string()));
+        assert_eq!(undo(&mut manager, "A000"), Ok("編輯A000".to_string()));
+        assert_eq!(tip(&manager, "A000"), None);
+        assert_eq!(tip(&manager, "A001"), Some("法力".to_string()));
+        assert!(undo(&mut manager, "A000").is_err());
+
+        assert_eq!(undo(&mut manager, "A001"), Ok("編輯A001".to_string()));
+        assert_eq!(redo(&mut manager, "A000"), Ok("編輯A000".to_string()));
+        assert!(redo(&mut manager, "A000").is_err());
+        assert_eq!(redo(&mut manager, "A001"), Ok("編輯A001".to_string()));
+        assert_eq!(redo(&mut manager, "A000"), Ok("取代".to_string()));
+        assert_eq!(tip(&manager, "A001"), Some("魔能".to_string()));
+    }
+
+    #[test]
+    fn test_undo_restores_flags_and_statuses() {
+        let mut manager = manager();
+        let first = SegmentKey::new("A000", FieldType::Tip, 0);
+        let second = SegmentKey::new("A001", FieldType::Tip, 0);
+        edit(&mut manager, "A000", "魔力");
+        manager
+            .statuses
+            .insert(first.clone(), SegmentStatus::Approved);
+
+        let snapshot = Snapshot::take_all(&manager);
+        propagate_segment(&mut manager, &first);
+        assert!(snapshot.record(&mut manager, "自動套用"));
+        assert_eq!(tip(&manager, "A001"), Some("魔力".to_string()));
+        let propagated = manager.review_flags.get(&second).cloned();
+        assert_eq!(propagated, Some(ReviewFlag::AutoPropagated));
+        let propagated_status = manager.statuses.get(&second).cloned();
+
+        assert_eq!(undo(&mut manager, "A000"), Ok("自動套用".to_string()));
+        assert_eq!(tip(&manager, "A001"), None);
+        assert!(!manager.review_flags.contains_key(&second));
+        assert!(!manager.statuses.contains_key(&second));
+        assert_eq!(manager.statuses.get(&first), Some(&SegmentStatus::Approved));
+
+        assert_eq!(redo(&mut manager, "A000"), Ok("自動套用".to_string()));
+        assert_eq!(manager.review_flags.get(&second).cloned(), propagated);
+        assert_eq!(manager.statuses.get(&second).cloned(), propagated_status);
+
+        // 只改變狀態也會寫入記錄
+        let snapshot = Snapshot::take(&manager, ["A000"]);
+        manager.statuses.remove(&first);
+        assert!(!snapshot.translation_changed(&manager));
+        assert!(snapshot.record(&mut manager, "變更狀態"));
+        undo(&mut manager, "A000").unwrap();
+        assert_eq!(manager.statuses.get(&first), Some(&SegmentStatus::Approved));
+    }
+
+    #[test]
+    fn test_per_object_undo_keeps_newer_edits() {
+        let mut manager = manager();
+        manager.history.set_scope(HistoryScope::PerObject);
+        edit(&mut manager, "A000", "魔力");
+        edit(&mut manager, "A001", "法力");
+
+        let snapshot = Snapshot::take_all(&manager);
+        for (id, value) in [("A000", "魔法"), ("A001", "魔能")] {
+            let key = SegmentKey::new(id, FieldType::Tip, 0);
+            manager.set_translation(&key, value.to_string());
+        }
+        snapshot.record(&mut manager, "取代");
+        edit(&mut manager, "A001", "能量");
+
+        // A001在取代之後又被編輯，要先在A001復原才能復原取代
+        assert!(undo(&mut manager, "A000").unwrap_err().contains("A001"));
+        assert_eq!(tip(&manager, "A000"), Some("魔法".to_string()));
+        assert_eq!(tip(&manager, "A001"), Some("能量".to_string()));
+
+        assert_eq!(undo(&mut manager, "A001"), Ok("編輯A001".to_string()));
+        assert_eq!(undo(&mut manager, "A000"), Ok("取代".to_string()));
+        assert_eq!(tip(&manager, "A000"), Some("魔力".to_string()));
+        assert_eq!(tip(&manager, "A001"), Some("法力".to_string()));
+
+        // 重做時也要先重做A001較早的操作
+        assert_eq!(undo(&mut manager, "A001"), Ok("編輯A001".to_string()));
+        assert!(redo(&mut manager, "A000").unwrap_err().contains("A001"));
+        assert_eq!(redo(&mut manager, "A001"), Ok("編輯A001".to_string()));
+        assert_eq!(redo(&mut manager, "A000"), Ok("取代".to_string()));
+        assert_eq!(redo(&mut manager, "A001"), Ok("編輯A001".to_string()));
+        assert_eq!(tip(&manager, "A001"), Some("能量".to_string()));
+    }
+}
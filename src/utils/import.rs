@@ -0,0 +1,201 @@
+use crate::data::tooltip::{SkillData, SkillManager};
+use crate::utils::common::*;
+use crate::utils::parser::parse_file;
+use std::collections::{BTreeMap, HashMap};
+
+pub const REFERENCE_SOURCE_FILE_NAME: &str = "reference_source.ini";
+pub const REFERENCE_TRANSLATE_FILE_NAME: &str = "reference_translation.ini";
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum MatchKind {
+    Id,
+    SourceText,
+}
+
+impl MatchKind {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            MatchKind::Id => "ID",
+            MatchKind::SourceText => "原文",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportCandidate {
+    pub target_id: String,
+    pub reference_id: String,
+    pub field_type: FieldType,
+    pub match_kind: MatchKind,
+    // 以ID比對時參考原文與當前原文不同
+    pub source_changed: bool,
+    pub value: Vec<String>,
+    pub selected: bool,
+}
+
+pub struct ReferenceData {
+    pub skills: BTreeMap<String, SkillData>,
+    pub translation_skills: BTreeMap<String, SkillData>,
+}
+
+impl ReferenceData {
+    pub fn load(source_path: &str, translation_path: &str) -> Result<Self, String> {
+        Ok(Self {
+            skills: parse_file(source_path)?,
+            translation_skills: parse_file(translation_path)?,
+        })
+    }
+}
+
+pub fn find_candidates(manager: &SkillManager, reference: &ReferenceData) -> Vec<ImportCandidate> {
+    // 以原文內容建立索引，只收錄有翻譯的欄位
+    let mut text_index: HashMap<&Vec<String>, (&String, &Vec<String>)> = HashMap::new();
+    for (id, source) in &reference.skills {
+        let Some(translated) = reference.translation_skills.get(id) else {
+            continue;
+        };
+        for field_type in FieldType::ALL.iter() {
+            let source_value = source.get_field(field_type);
+            let translated_value = translated.get_field(field_type);
+            if source_value.is_empty() || translated_value.len() != source_value.len() {
+                continue;
+            }
+            text_index
+                .entry(source_value)
+                .or_insert((id, translated_value));
+        }
+    }
+
+    let mut candidates = vec![];
+    for (id, source) in &manager.skills {
+        let current = manager.translation_skills.get(id);
+        for field_type in FieldType::ALL.iter() {
+            let source_value = source.get_field(field_type);
+            if source_value.is_empty() {
+                continue;
+            }
+
+            let by_id = reference.translation_skills.get(id).and_then(|translated| {
+                let value = translated.get_field(field_type);
+                if value.len() != source_value.len() {
+                    return None;
+                }
+                let reference_source = reference.skills.get(id).map(|s| s.get_field(field_type));
+                Some((id, value, reference_source != Some(source_value)))
+            });
+
+            let (reference_id, value, match_kind, source_changed) = match by_id {
+                Some((reference_id, value, changed)) => {
+                    (reference_id, value, MatchKind::Id, changed)
+                }
+                None => match text_index.get(source_value) {
+                    Some((reference_id, value)) => {
+                        (*reference_id, *value, MatchKind::SourceText, false)
+                    }
+                    None => continue,
+                },
+            };
+
+            let current_value = current.map(|data| data.get_field(field_type));
+            if current_value == Some(value) {
+                continue;
+            }
+
+            candidates.push(ImportCandidate {
+                target_id: id.clone(),
+                reference_id: reference_id.clone(),
+                field_type: field_type.clone(),
+                match_kind,
+                source_changed,
+                value: value.clone(),
+                selected: current_value.is_none_or(|v| v.is_empty()) && !source_changed,
+            });
+        }
+    }
+
+    candidates
+}
+
+pub fn apply_candidates(manager: &mut SkillManager, candidates: &[ImportCandidate]) -> usize {
+    let mut count = 0;
+    for candidate in candidates.iter().filter(|c| c.selected) {
+        let Some(source) = manager.skills.get(&candidate.target_id) else {
+            continue;
+        };
+        let Some(text_type) = source.text_type_map.get(&candidate.field_type).cloned() else {
+            continue;
+        };
+
+        let translated = manager
+            .translation_skills
+            .entry(candidate.target_id.clone())
+            .or_insert_with(|| SkillData {
+                id: candidate.target_id.clone(),
+                ..Default::default()
+            });
+        translated.insert_data(
+            text_type,
+            candidate.field_type.clone(),
+            candidate.value.clone(),
+        );
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{manager, skill_map, tip_skill};
+
+    #[test]
+    fn test_find_candidates_by_id_then_source_text() {
+        let manager = manager(
+            vec![
+                tip_skill("A000", &["무공", "무공2"]),
+                tip_skill("A001", &["일도양단"]),
+                tip_skill("A002", &["없음"]),
+            ],
+            vec![],
+        );
+        let reference = ReferenceData {
+            skills: skill_map(vec![
+                tip_skill("A000", &["무공", "무공2"]),
+                tip_skill("B001", &["일도양단"]),
+            ]),
+            translation_skills: skill_map(vec![
+                tip_skill("A000", &["武功", "武功2"]),
+                tip_skill("B001", &["一刀兩斷"]),
+            ]),
+        };
+
+        let candidates = find_candidates(&manager, &reference);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].target_id, "A000");
+        assert_eq!(candidates[0].match_kind, MatchKind::Id);
+        assert!(!candidates[0].source_changed);
+        assert_eq!(candidates[1].target_id, "A001");
+        assert_eq!(candidates[1].reference_id, "B001");
+        assert_eq!(candidates[1].match_kind, MatchKind::SourceText);
+        assert_eq!(candidates[1].value, vec!["一刀兩斷"]);
+    }
+
+    #[test]
+    fn test_apply_candidates() {
+        let mut manager = manager(vec![tip_skill("A001", &["일도양단"])], vec![]);
+        let reference = ReferenceData {
+            skills: skill_map(vec![tip_skill("B001", &["일도양단"])]),
+            translation_skills: skill_map(vec![tip_skill("B001", &["一刀兩斷"])]),
+        };
+
+        let candidates = find_candidates(&manager, &reference);
+        assert_eq!(apply_candidates(&mut manager, &candidates), 1);
+        let translated = manager.translation_skills.get("A001").unwrap();
+        assert_eq!(translated.tip, vec!["一刀兩斷"]);
+        assert_eq!(
+            translated.text_type_map.get(&FieldType::Tip),
+            Some(&TextType::SingleLineArray)
+        );
+        assert!(find_candidates(&manager, &reference).is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+use crate::data::tooltip::{SegmentKey, SegmentStatus, SkillManager};
+use crate::utils::comment::{format_timestamp, Comment, Comments};
+use crate::utils::common::*;
+use std::fs;
+
+pub const XLIFF_FILE_NAME: &str = "translation.xlf";
+pub const PO_FILE_NAME: &str = "translation.po";
+const SOURCE_LANGUAGE: &str = "ko";
+
+// 交換格式中的段落識別，例如 A000/Tip/1
+fn unit_id(key: &SegmentKey) -> String {
+    format!("{}/{}/{}", key.id, key.field_type.to_str(), key.level + 1)
+}
+
+fn note_text(comment: &Comment) -> String {
+    let mut text = format!("{} ({})", comment.text, format_timestamp(comment.timestamp));
+    if comment.resolved {
+        text.push_str(" [已解決]");
+    }
+    text
+}
+
+fn xliff_state(status: &SegmentStatus) -> &'static str {
+    match status {
+        SegmentStatus::Untranslated => "new",
+        SegmentStatus::MachineFilled => "needs-review-translation",
+        SegmentStatus::Draft => "needs-review-translation",
+        SegmentStatus::Translated => "translated",
+        SegmentStatus::Reviewed => "signed-off",
+        SegmentStatus::Approved => "final",
+    }
+}
+
+// XLIFF 1.2，留言輸出為note，目標語言使用QA設定中的語言
+pub fn output_xliff(manager: &SkillManager, comments: &Comments, language: &str) -> String {
+    let mut output = String::new();
+    output.push_line(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    output.push_line(r#"<xliff version="1.2" xmlns="urn:oasis:names:tc:xliff:document:1.2">"#);
+    output.push_line(&format!(
+        r#"  <file original="{}" source-language="{}" target-language="{}" datatype="plaintext">"#,
+        SOURCE_FILE_NAME, SOURCE_LANGUAGE, language
+    ));
+    output.push_line("    <body>");
+    for key in manager.segment_keys() {
+        let Some(source) = manager.get_source(&key) else {
+            continue;
+        };
+        let status = manager.status(&key);
+        output.push_line(&format!(
+            r#"      <trans-unit id="{}" xml:space="preserve">"#,
+            escape_xml(&unit_id(&key))
+        ));
+        output.push_line(&format!("        <source>{}</source>", escape_xml(source)));
+        if let Some(translation) = manager
+            .get_translation(&key)
+            .filter(|_| manager.is_translated(&key))
+        {
+            output.push_line(&format!(
+                r#"        <target state="{}">{}</target>"#,
+                xliff_state(&status),
+                escape_xml(translation)
+            ));
+        }
+        for comment in comments.thread(&key) {
+            output.push_line(&format!(
+                r#"        <note from="{}">{}</note>"#,
+                escape_xml(&comment.author),
+                escape_xml(&note_text(comment))
+            ));
+        }
+        output.push_line("      </trans-unit>");
+    }
+    output.push_line("    </body>");
+    output.push_line("  </file>");
+    output.push_line("</xliff>");
+    output
+}
+
+fn escape_po(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+// gettext PO，留言輸出為譯者註解，尚未定稿的翻譯標記為fuzzy
+pub fn output_po(manager: &SkillManager, comments: &Comments, language: &str) -> String {
+    let mut output = String::new();
+    output.push_line("msgid \"\"");
+    output.push_line("msgstr \"\"");
+    output.push_line("\"Content-Type: text/plain; charset=UTF-8\\n\"");
+    output.push_line(&format!("\"Language: {}\\n\"", language));
+
+    for key in manager.segment_keys() {
+        let Some(source) = manager.get_source(&key) else {
+            continue;
+        };
+        output.push(NEWLINE_SYMBOL);
+        for comment in comments.thread(&key) {
+            for (i, line) in note_text(comment).lines().enumerate() {
+                match i {
+                    0 => output.push_line(&format!("# {}: {}", comment.author, line)),
+                    _ => output.push_line(&format!("# {}", line)),
+                }
+            }
+        }
+        output.push_line(&format!("#. {}", key.to_label()));
+
+        let status = manager.status(&key);
+        let translation = match manager.is_translated(&key) {
+            true => manager.get_translation(&key).cloned().unwrap_or_default(),
+            false => String::new(),
+        };
+        if matches!(status, SegmentStatus::MachineFilled | SegmentStatus::Draft) {
+            output.push_line("#, fuzzy");
+        }
+        output.push_line(&format!("msgctxt \"{}\"", escape_po(&unit_id(&key))));
+        output.push_line(&format!("msgid \"{}\"", escape_po(source)));
+        output.push_line(&format!("msgstr \"{}\"", escape_po(&translation)));
+    }
+    output
+}
+
+pub fn export_xliff(
+    path: &str,
+    manager: &SkillManager,
+    comments: &Comments,
+    language: &str,
+) -> Result<(), String> {
+    let output = output_xliff(manager, comments, language);
+    fs::write(path, output).map_err(|e| format!("{}: {}", path, e))
+}
+
+pub fn export_po(
+    path: &str,
+    manager: &SkillManager,
+    comments: &Comments,
+    language: &str,
+) -> Result<(), String> {
+    let output = output_po(manager, comments, language);
+    fs::write(path, output).map_err(|e| format!("{}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{self, tip_skill};
+    use crate::data::tooltip::ReviewFlag;
+
+    fn manager() -> SkillManager {
+        let mut manager = test_util::manager(
+            vec![tip_skill("A000", &["<레벨 1>", "레벨 2"])],
+            vec![tip_skill("A000", &["<等級 1>", "레벨 2"])],
+        );
+        manager.review_flags.insert(
+            SegmentKey::new("A000", FieldType::Tip, 0),
+            ReviewFlag::Generated,
+        );
+        manager
+    }
+
+    fn comments() -> Comments {
+        let mut comments = Comments::default();
+        comments.add(
+            &SegmentKey::new("A000", FieldType::Tip, 1),
+            "reviewer",
+            "英雄名稱待定",
+            0,
+        );
+        comments
+    }
+
+    #[test]
+    fn test_output_xliff() {
+        let output = output_xliff(&manager(), &comments(), "ja");
+        assert!(output.contains(r#"source-language="ko" target-language="ja""#));
+        assert!(output.contains(
+            "<trans-unit id=\"A000/Tip/1\" xml:space=\"preserve\">\n        <source>&lt;레벨 1&gt;</source>\n        <target state=\"needs-review-translation\">&lt;等級 1&gt;</target>\n      </trans-unit>"
+        ));
+        assert!(output.contains(
+            "<source>레벨 2</source>\n        <note from=\"reviewer\">英雄名稱待定 (1970-01-01 00:00)</note>\n      </trans-unit>"
+        ));
+    }
+
+    #[test]
+    fn test_output_po() {
+        let output = output_po(&manager(), &comments(), "ja");
+        assert!(output.contains("\"Language: ja\\n\""));
+        assert!(output.contains(
+            "#. [A000] Tip #1\n#, fuzzy\nmsgctxt \"A000/Tip/1\"\nmsgid \"<레벨 1>\"\nmsgstr \"<等級 1>\"\n"
+        ));
+        assert!(output.contains(
+            "# reviewer: 英雄名稱待定 (1970-01-01 00:00)\n#. [A000] Tip #2\nmsgctxt \"A000/Tip/2\"\nmsgid \"레벨 2\"\nmsgstr \"\"\n"
+        ));
+    }
+}
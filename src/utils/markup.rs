@@ -0,0 +1,217 @@
+use crate::utils::common::NEWLINE_SYMBOL;
+use std::ops::Range;
+
+pub const COLOR_HEX_LENGTH: usize = 8;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum MarkupKind {
+    // |cAARRGGBB，色碼統一轉為小寫
+    Color(String),
+    // |c 後面不是8碼16進位
+    InvalidColor,
+    // |r
+    Reset,
+    // |n
+    Newline,
+    Text,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MarkupSpan {
+    pub kind: MarkupKind,
+    // 在原字串中的位元組位置
+    pub range: Range<usize>,
+}
+
+pub fn tokenize_markup(text: &str) -> Vec<MarkupSpan> {
+    let bytes = text.as_bytes();
+    let mut spans: Vec<MarkupSpan> = vec![];
+    let mut text_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'|' || i + 1 >= bytes.len() {
+            i += 1;
+            continue;
+        }
+
+        let (kind, end) = match bytes[i + 1].to_ascii_lowercase() {
+            b'c' => {
+                let hex_end = (i + 2..bytes.len().min(i + 2 + COLOR_HEX_LENGTH))
+                    .find(|&j| !bytes[j].is_ascii_hexdigit())
+                    .unwrap_or(bytes.len().min(i + 2 + COLOR_HEX_LENGTH));
+                if hex_end - (i + 2) == COLOR_HEX_LENGTH {
+                    let hex = text[i + 2..hex_end].to_ascii_lowercase();
+                    (MarkupKind::Color(hex), hex_end)
+                } else {
+                    (MarkupKind::InvalidColor, hex_end)
+                }
+            }
+            b'r' => (MarkupKind::Reset, i + 2),
+            b'n' => (MarkupKind::Newline, i + 2),
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+
+        if text_start < i {
+            spans.push(MarkupSpan {
+                kind: MarkupKind::Text,
+                range: text_start..i,
+            });
+        }
+        spans.push(MarkupSpan {
+            kind,
+            range: i..end,
+        });
+        text_start = end;
+        i = end;
+    }
+
+    if text_start < text.len() {
+        spans.push(MarkupSpan {
+            kind: MarkupKind::Text,
+            range: text_start..text.len(),
+        });
+    }
+    spans
+}
+
+// 移除色碼只保留顯示的文字，|n 轉為換行
+pub fn strip_markup(text: &str) -> String {
+    tokenize_markup(text)
+        .into_iter()
+        .filter_map(|span| match span.kind {
+            MarkupKind::Text => Some(&text[span.range]),
+            MarkupKind::Newline => Some("\n"),
+            _ => None,
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct DisplayLine {
+    // 在原字串中的位元組位置，不含換行符號
+    pub range: Range<usize>,
+    pub width: usize,
+}
+
+// 全形字元佔兩格
+pub fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+// 依實際顯示切分行並計算寬度，色碼不佔寬度，|n 與換行都會換行
+pub fn display_lines(text: &str) -> Vec<DisplayLine> {
+    let mut lines = vec![];
+    let mut start = 0;
+    let mut width = 0;
+    for span in tokenize_markup(text) {
+        match span.kind {
+            MarkupKind::Text => {
+                for (i, c) in text[span.range.clone()].char_indices() {
+                    let index = span.range.start + i;
+                    if c == NEWLINE_SYMBOL {
+                        lines.push(DisplayLine {
+                            range: start..index,
+                            width,
+                        });
+                        start = index + 1;
+                        width = 0;
+                    } else if c != '\r' {
+                        width += char_width(c);
+                    }
+                }
+            }
+            MarkupKind::Newline => {
+                lines.push(DisplayLine {
+                    range: start..span.range.start,
+                    width,
+                });
+                start = span.range.end;
+                width = 0;
+            }
+            _ => {}
+        }
+    }
+    lines.push(DisplayLine {
+        range: start..text.len(),
+        width,
+    });
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_markup() {
+        let text = "|c00ffff80무공|CFFFFCC00(A)|r|n|cxyz|R";
+        let kinds: Vec<MarkupKind> = tokenize_markup(text).into_iter().map(|s| s.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                MarkupKind::Color("00ffff80".to_string()),
+                MarkupKind::Text,
+                MarkupKind::Color("ffffcc00".to_string()),
+                MarkupKind::Text,
+                MarkupKind::Reset,
+                MarkupKind::Newline,
+                MarkupKind::InvalidColor,
+                MarkupKind::Text,
+                MarkupKind::Reset,
+            ]
+        );
+
+        let spans = tokenize_markup("ab|c1234|r");
+        assert_eq!(spans[1].range, 2..8);
+        assert_eq!(spans[2].range, 8..10);
+    }
+
+    #[test]
+    fn test_strip_markup() {
+        assert_eq!(
+            strip_markup("|c009E0ADD데미지|r : 1000|n|cffffcc00레벨 1|r |"),
+            "데미지 : 1000\n레벨 1 |"
+        );
+    }
+
+    #[test]
+    fn test_display_lines() {
+        let text = " |cffffcc00레벨 1|r - 40%|nAB\n";
+        let lines = display_lines(text);
+        assert_eq!(
+            lines,
+            vec![
+                DisplayLine {
+                    range: 0..27,
+                    width: 13,
+                },
+                DisplayLine {
+                    range: 29..31,
+                    width: 2,
+                },
+                DisplayLine {
+                    range: 32..32,
+                    width: 0,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use crate::data::tooltip::{SegmentKey, SkillData};
+use crate::utils::common::FieldType;
+use crate::utils::diff::{extract_numbers, tokenize, TokenKind};
+use crate::utils::similarity::similarity;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+pub const MEMORY_SUGGESTION_LIMIT: usize = 5;
+pub const MEMORY_THRESHOLD: f32 = 0.5;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum MemoryOrigin {
+    Project(SegmentKey),
+    Imported(SegmentKey),
+}
+
+impl MemoryOrigin {
+    pub fn to_label(&self) -> String {
+        match self {
+            MemoryOrigin::Project(key) => key.to_label(),
+            MemoryOrigin::Imported(key) => format!("匯入 {}", key.to_label()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MemoryEntry {
+    pub source: String,
+    pub target: String,
+    pub origin: MemoryOrigin,
+}
+
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub entry: MemoryEntry,
+    pub score: f32,
+    // 已將數值替換成目前原文的翻譯
+    pub adapted: String,
+}
+
+#[derive(Default)]
+pub struct TranslationMemory {
+    pub entries: Vec<MemoryEntry>,
+}
+
+impl TranslationMemory {
+    pub fn from_skills(
+        skills: &BTreeMap<String, SkillData>,
+        translation_skills: &BTreeMap<String, SkillData>,
+        imported: bool,
+    ) -> Self {
+        let mut entries = vec![];
+        for (id, translated) in translation_skills {
+            let Some(source) = skills.get(id) else {
+                continue;
+            };
+            for field_type in FieldType::ALL.iter() {
+                let pairs = source
+                    .get_field(field_type)
+                    .iter()
+                    .zip(translated.get_field(field_type));
+                for (level, (source_text, target_text)) in pairs.enumerate() {
+                    // 直接複製原文的視為尚未翻譯
+                    if target_text.is_empty() || source_text == target_text {
+                        continue;
+                    }
+                    let key = SegmentKey::new(id, field_type.clone(), level);
+                    entries.push(MemoryEntry {
+                        source: source_text.clone(),
+                        target: target_text.clone(),
+                        origin: match imported {
+                            true => MemoryOrigin::Imported(key),
+                            false => MemoryOrigin::Project(key),
+                        },
+                    });
+                }
+            }
+        }
+        Self { entries }
+    }
+
+    pub fn suggest(
+        &self,
+        source: &str,
+        exclude: Option<&SegmentKey>,
+        limit: usize,
+    ) -> Vec<Suggestion> {
+        let mut suggestions: Vec<Suggestion> = vec![];
+        for entry in &self.entries {
+            if let (Some(exclude), MemoryOrigin::Project(key)) = (exclude, &entry.origin) {
+                if key == exclude {
+                    continue;
+                }
+            }
+
+            let score = similarity(&entry.source, source);
+            if score < MEMORY_THRESHOLD {
+                continue;
+            }
+
+            // 相同原文與翻譯只保留一筆
+            if suggestions
+                .iter()
+                .any(|s| s.entry.source == entry.source && s.entry.target == entry.target)
+            {
+                continue;
+            }
+
+            suggestions.push(Suggestion {
+                entry: entry.clone(),
+                score,
+                adapted: adapt_numbers(&entry.source, &entry.target, source),
+            });
+        }
+
+        suggestions.sort_by(|a, b| b.score.total_cmp(&a.score));
+        suggestions.truncate(limit);
+        suggestions
+    }
+}
+
+// 依照原文中數值的變化，把翻譯中對應的數值換成目前原文的數值
+pub fn adapt_numbers(memory_source: &str, memory_target: &str, current_source: &str) -> String {
+    let old_numbers = extract_numbers(memory_source);
+    let new_numbers = extract_numbers(current_source);
+    if old_numbers.len() != new_numbers.len() {
+        return memory_target.to_string();
+    }
+
+    let mut replacements: HashMap<&str, VecDeque<&str>> = HashMap::new();
+    for (old, new) in old_numbers.iter().zip(new_numbers.iter()) {
+        replacements
+            .entry(old.text)
+            .or_default()
+            .push_back(new.text);
+    }
+
+    let mut result = String::new();
+    for token in tokenize(memory_target) {
+        let replacement = match token.kind {
+            TokenKind::Number => replacements
+                .get_mut(token.text)
+                .and_then(|queue| queue.pop_front()),
+            _ => None,
+        };
+        result.push_str(replacement.unwrap_or(token.text));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{skill, skill_map};
+    use crate::utils::common::TextType;
+
+    #[test]
+    fn test_adapt_numbers() {
+        assert_eq!(
+            adapt_numbers(
+                "데미지 : 1000, 쿨다운 : 45초",
+                "傷害：1000，冷卻：45秒",
+                "데미지 : 1350, 쿨다운 : 40초"
+            ),
+            "傷害：1350，冷卻：40秒"
+        );
+        assert_eq!(
+            adapt_numbers("레벨 1 - 1000", "等級1 - 1000", "레벨 1 - 1000 - 3"),
+            "等級1 - 1000"
+        );
+    }
+
+    #[test]
+    fn test_suggest() {
+        let skills = skill_map(vec![
+            skill(
+                "A000",
+                TextType::MultiLineArray,
+                &[(FieldType::Ubertip, &["데미지 : 1000", "데미지 : 1350"])],
+            ),
+            skill(
+                "A001",
+                TextType::MultiLineArray,
+                &[(FieldType::Ubertip, &["마력을 흡수합니다"])],
+            ),
+        ]);
+        let translation_skills = skill_map(vec![
+            skill(
+                "A000",
+                TextType::MultiLineArray,
+                &[(FieldType::Ubertip, &["傷害：1000", "데미지 : 1350"])],
+            ),
+            skill(
+                "A001",
+                TextType::MultiLineArray,
+                &[(FieldType::Ubertip, &["吸收魔力"])],
+            ),
+        ]);
+        let memory = TranslationMemory::from_skills(&skills, &translation_skills, false);
+        assert_eq!(memory.entries.len(), 2);
+
+        let current = SegmentKey::new("A000", FieldType::Ubertip, 1);
+        let suggestions = memory.suggest("데미지 : 1350", Some(&current), MEMORY_SUGGESTION_LIMIT);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].entry.target, "傷害：1000");
+        assert_eq!(suggestions[0].adapted, "傷害：1350");
+
+        let itself = SegmentKey::new("A000", FieldType::Ubertip, 0);
+        let suggestions = memory.suggest("데미지 : 1000", Some(&itself), MEMORY_SUGGESTION_LIMIT);
+        assert!(suggestions.is_empty());
+    }
+}
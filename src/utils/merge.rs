@@ -0,0 +1,238 @@
+use crate::data::tooltip::{SegmentKey, SkillData};
+use crate::utils::common::{ContextType, FieldType, TextType};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub const MERGE_BASE_FILE_NAME: &str = "translation_base.ini";
+
+// 衝突段落的其中一個版本，None代表該檔案沒有這個段落
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeChoice {
+    pub label: String,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    pub key: SegmentKey,
+    // 第一個是共同的基準版本
+    pub choices: Vec<MergeChoice>,
+    pub selected: Option<usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct MergeResult {
+    segments: BTreeMap<SegmentKey, Option<String>>,
+    text_types: BTreeMap<(String, FieldType), Vec<(usize, TextType)>>,
+    contexts: BTreeMap<String, BTreeMap<ContextType, String>>,
+    pub auto_merged: usize,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+fn segment_value(skills: &BTreeMap<String, SkillData>, key: &SegmentKey) -> Option<String> {
+    skills
+        .get(&key.id)?
+        .get_field(&key.field_type)
+        .get(key.level)
+        .cloned()
+}
+
+// 以段落為單位合併，只有一個檔案修改的段落直接採用，多個檔案改成不同內容時列為衝突
+pub fn merge_translations(
+    base: &BTreeMap<String, SkillData>,
+    edits: &[(String, BTreeMap<String, SkillData>)],
+) -> MergeResult {
+    let files: Vec<&BTreeMap<String, SkillData>> = std::iter::once(base)
+        .chain(edits.iter().map(|(_, skills)| skills))
+        .collect();
+
+    let mut result = MergeResult::default();
+    let mut keys = BTreeSet::new();
+    for skills in &files {
+        for (id, data) in skills.iter() {
+            let context = result.contexts.entry(id.clone()).or_default();
+            context.extend(data.context.clone());
+            for field_type in FieldType::ALL.iter() {
+                let values = data.get_field(field_type);
+                if let Some(text_type) = data.text_type_map.get(field_type) {
+                    result
+                        .text_types
+                        .entry((id.clone(), field_type.clone()))
+                        .or_default()
+                        .push((values.len(), text_type.clone()));
+                }
+                for level in 0..values.len() {
+                    keys.insert(SegmentKey::new(id, field_type.clone(), level));
+                }
+            }
+        }
+    }
+
+    for key in keys {
+        let base_value = segment_value(base, &key);
+        let mut changed: Vec<Option<String>> = vec![];
+        for (_, skills) in edits {
+            let value = segment_value(skills, &key);
+            if value != base_value && !changed.contains(&value) {
+                changed.push(value);
+            }
+        }
+
+        match changed.len() {
+            0 => {
+                result.segments.insert(key, base_value);
+            }
+            1 => {
+                result.auto_merged += 1;
+                result.segments.insert(key, changed.remove(0));
+            }
+            _ => {
+                let choices = std::iter::once(MergeChoice {
+                    label: "基準".to_string(),
+                    value: base_value.clone(),
+                })
+                .chain(edits.iter().map(|(label, skills)| MergeChoice {
+                    label: label.clone(),
+                    value: segment_value(skills, &key),
+                }))
+                .collect();
+                result.segments.insert(key.clone(), base_value);
+                result.conflicts.push(MergeConflict {
+                    key,
+                    choices,
+                    selected: None,
+                });
+            }
+        }
+    }
+
+    result
+}
+
+impl MergeResult {
+    pub fn unresolved(&self) -> usize {
+        self.conflicts
+            .iter()
+            .filter(|conflict| conflict.selected.is_none())
+            .count()
+    }
+
+    // 套用衝突的選擇後產生合併的翻譯
+    pub fn merged_skills(&self) -> Result<BTreeMap<String, SkillData>, String> {
+        let unresolved = self.unresolved();
+        if unresolved > 0 {
+            return Err(format!("還有{}個衝突尚未解決", unresolved));
+        }
+
+        let mut segments = self.segments.clone();
+        for conflict in &self.conflicts {
+            let Some(choice) = conflict.selected.and_then(|i| conflict.choices.get(i)) else {
+                continue;
+            };
+            segments.insert(conflict.key.clone(), choice.value.clone());
+        }
+
+        let mut fields: BTreeMap<(String, FieldType), Vec<Option<String>>> = BTreeMap::new();
+        for (key, value) in segments {
+            let field = fields
+                .entry((key.id.clone(), key.field_type.clone()))
+                .or_default();
+            field.resize(key.level + 1, None);
+            field[key.level] = value;
+        }
+
+        let mut skills = BTreeMap::new();
+        for ((id, field_type), mut values) in fields {
+            // 刪除的等級只會在最後面，中間缺少的等級以空字串補上
+            while values.last().is_some_and(|value| value.is_none()) {
+                values.pop();
+            }
+            if values.is_empty() {
+                continue;
+            }
+            let values: Vec<String> = values.into_iter().map(Option::unwrap_or_default).collect();
+            let text_type = self.text_type(&id, &field_type, values.len());
+            let data = skills.entry(id.clone()).or_insert_with(|| SkillData {
+                id: id.clone(),
+                context: self.contexts.get(&id).cloned().unwrap_or_default(),
+                ..Default::default()
+            });
+            data.insert_data(text_type, field_type, values);
+        }
+        Ok(skills)
+    }
+
+    // 優先使用等級數相同的檔案的文字格式
+    fn text_type(&self, id: &str, field_type: &FieldType, len: usize) -> TextType {
+        let Some(types) = self.text_types.get(&(id.to_string(), field_type.clone())) else {
+            return TextType::default();
+        };
+        types
+            .iter()
+            .find(|(count, _)| *count == len)
+            .or(types.first())
+            .map(|(_, text_type)| text_type.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{skill_map, tip_skill};
+
+    fn skills(tip: &[&str]) -> BTreeMap<String, SkillData> {
+        skill_map(vec![tip_skill("A000", tip)])
+    }
+
+    #[test]
+    fn test_merge_without_conflicts() {
+        let base = skills(&["레벨 1", "레벨 2", "레벨 3"]);
+        let edits = vec![
+            ("a.ini".to_string(), skills(&["等級 1", "레벨 2", "레벨 3"])),
+            ("b.ini".to_string(), skills(&["레벨 1", "等級 2", "레벨 3"])),
+            ("c.ini".to_string(), skills(&["等級 1", "레벨 2"])),
+        ];
+
+        let result = merge_translations(&base, &edits);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(result.auto_merged, 3);
+        let merged = result.merged_skills().unwrap();
+        assert_eq!(
+            merged["A000"].tip,
+            vec!["等級 1".to_string(), "等級 2".to_string()]
+        );
+        assert_eq!(
+            merged["A000"].text_type_map[&FieldType::Tip],
+            TextType::SingleLineArray
+        );
+    }
+
+    #[test]
+    fn test_merge_conflicts() {
+        let base = skills(&["레벨 1", "레벨 2"]);
+        let edits = vec![
+            ("a.ini".to_string(), skills(&["等級 1", "레벨 2"])),
+            ("b.ini".to_string(), skills(&["第1級", "等級 2"])),
+        ];
+
+        let mut result = merge_translations(&base, &edits);
+        assert_eq!(result.auto_merged, 1);
+        assert_eq!(result.conflicts.len(), 1);
+        let conflict = &result.conflicts[0];
+        assert_eq!(conflict.key, SegmentKey::new("A000", FieldType::Tip, 0));
+        let values: Vec<Option<&str>> = conflict
+            .choices
+            .iter()
+            .map(|choice| choice.value.as_deref())
+            .collect();
+        assert_eq!(values, vec![Some("레벨 1"), Some("等級 1"), Some("第1級")]);
+        assert!(result.merged_skills().is_err());
+
+        result.conflicts[0].selected = Some(2);
+        let merged = result.merged_skills().unwrap();
+        assert_eq!(
+            merged["A000"].tip,
+            vec!["第1級".to_string(), "等級 2".to_string()]
+        );
+    }
+}
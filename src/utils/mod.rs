@@ -1,3 +1,27 @@
 pub mod parser;
 pub mod export;
-pub mod common;
\ No newline at end of file
+pub mod common;
+pub mod import;
+pub mod remap;
+pub mod similarity;
+pub mod diff;
+pub mod memory;
+pub mod propagate;
+pub mod glossary;
+pub mod qa;
+pub mod markup;
+pub mod script;
+pub mod generate;
+pub mod replace;
+pub mod reset;
+pub mod history;
+pub mod recovery;
+pub mod fingerprint;
+pub mod archive;
+pub mod update;
+pub mod merge;
+pub mod orphan;
+pub mod status;
+pub mod comment;
+pub mod interchange;
+pub mod review;
\ No newline at end of file
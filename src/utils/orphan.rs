@@ -0,0 +1,395 @@
+use crate::data::tooltip::{SegmentKey, SkillData, SkillManager};
+use crate::utils::archive::{field_data, merge_archive};
+use crate::utils::comment::Comments;
+use crate::utils::common::*;
+use crate::utils::fingerprint::SourceFingerprints;
+use std::collections::BTreeMap;
+
+// 翻譯中存在但原文已經沒有的物件或欄位，匯出時不會被使用
+#[derive(Debug, Clone, PartialEq)]
+pub struct Orphan {
+    pub id: String,
+    // None代表整個物件都不在原文中
+    pub field_type: Option<FieldType>,
+    pub preview: String,
+    pub selected: bool,
+    // 重新對應的目標ID
+    pub target_id: String,
+}
+
+impl Orphan {
+    pub fn to_label(&self) -> String {
+        match &self.field_type {
+            Some(field_type) => format!("[{}] {}", self.id, field_type.to_str()),
+            None => format!("[{}] 整個物件", self.id),
+        }
+    }
+}
+
+fn first_value(data: &SkillData, field_types: &[FieldType]) -> String {
+    field_types
+        .iter()
+        .find_map(|field_type| data.get_field(field_type).first())
+        .cloned()
+        .unwrap_or_default()
+}
+
+pub fn find_orphans(manager: &SkillManager) -> Vec<Orphan> {
+    let mut orphans = vec![];
+    for (id, data) in &manager.translation_skills {
+        let Some(source) = manager.skills.get(id) else {
+            orphans.push(Orphan {
+                id: id.clone(),
+                field_type: None,
+                preview: first_value(data, &FieldType::ALL),
+                selected: false,
+                target_id: String::new(),
+            });
+            continue;
+        };
+        for field_type in FieldType::ALL.iter() {
+            if data.get_field(field_type).is_empty() || !source.get_field(field_type).is_empty() {
+                continue;
+            }
+            orphans.push(Orphan {
+                id: id.clone(),
+                field_type: Some(field_type.clone()),
+                preview: first_value(data, std::slice::from_ref(field_type)),
+                selected: false,
+                target_id: String::new(),
+            });
+        }
+    }
+    orphans
+}
+
+fn take_orphan(manager: &mut SkillManager, orphan: &Orphan) -> Option<SkillData> {
+    take_translation(manager, &orphan.id, orphan.field_type.as_ref())
+}
+
+// 從翻譯中移除整個物件或單一欄位並回傳被移除的內容，field_type為None代表整個物件
+pub(crate) fn take_translation(
+    manager: &mut SkillManager,
+    id: &str,
+    field_type: Option<&FieldType>,
+) -> Option<SkillData> {
+    let Some(field_type) = field_type else {
+        manager.review_flags.retain(|key, _| key.id != id);
+        manager.statuses.retain(|key, _| key.id != id);
+        return manager.translation_skills.remove(id);
+    };
+
+    let removed = |key: &SegmentKey| key.id == id && &key.field_type == field_type;
+    manager.review_flags.retain(|key, _| !removed(key));
+    manager.statuses.retain(|key, _| !removed(key));
+    let translated = manager.translation_skills.get_mut(id)?;
+    let taken = field_data(translated, field_type);
+    translated.get_field_mut(field_type).clear();
+    translated.text_type_map.remove(field_type);
+    if !is_available_skill_data(translated) {
+        manager.translation_skills.remove(id);
+    }
+    taken
+}
+
+// 勾選的孤立翻譯內容，不修改翻譯，用來在移除前先寫入封存檔
+pub fn selected_orphans(manager: &SkillManager, orphans: &[Orphan]) -> BTreeMap<String, SkillData> {
+    let mut selected: BTreeMap<String, SkillData> = BTreeMap::new();
+    for orphan in orphans.iter().filter(|o| o.selected) {
+        let Some(translated) = manager.translation_skills.get(&orphan.id) else {
+            continue;
+        };
+        let data = match &orphan.field_type {
+            Some(field_type) => field_data(translated, field_type),
+            None => Some(translated.clone()),
+        };
+        if let Some(data) = data {
+            merge_archive(&mut selected, BTreeMap::from([(orphan.id.clone(), data)]));
+        }
+    }
+    selected
+}
+
+// 移除勾選的孤立翻譯，回傳被移除的內容
+pub fn take_orphans(manager: &mut SkillManager, orphans: &[Orphan]) -> BTreeMap<String, SkillData> {
+    let taken = selected_orphans(manager, orphans);
+    for orphan in orphans.iter().filter(|o| o.selected) {
+        take_orphan(manager, orphan);
+    }
+    taken
+}
+
+// 把屬於孤立翻譯的段落鍵值換成目標物件，不屬於搬移範圍的回傳None
+fn remapped_key(
+    key: &SegmentKey,
+    orphan_id: &str,
+    target_id: &str,
+    fields: &[FieldType],
+) -> Option<SegmentKey> {
+    (key.id == orphan_id && fields.contains(&key.field_type))
+        .then(|| SegmentKey::new(target_id, key.field_type.clone(), key.level))
+}
+
+fn move_entries<V>(
+    map: &mut BTreeMap<SegmentKey, V>,
+    remap: impl Fn(&SegmentKey) -> Option<SegmentKey>,
+) {
+    let keys: Vec<(SegmentKey, SegmentKey)> = map
+        .keys()
+        .filter_map(|key| Some((key.clone(), remap(key)?)))
+        .collect();
+    for (old, new) in keys {
+        if let Some(value) = map.remove(&old) {
+            map.insert(new, value);
+        }
+    }
+}
+
+// 把孤立的翻譯搬到原文中存在的物件，只搬移目標原文有的欄位，段落的狀態、標記、指紋與留言一起搬移
+pub fn remap_orphan(
+    manager: &mut SkillManager,
+    fingerprints: &mut SourceFingerprints,
+    comments: &mut Comments,
+    orphan: &Orphan,
+) -> Result<usize, String> {
+    let target_id = orphan.target_id.trim();
+    let Some(source) = manager.skills.get(target_id) else {
+        return Err(format!("原文中沒有[{}]", target_id));
+    };
+    let Some(data) = manager.translation_skills.get(&orphan.id) else {
+        return Err(format!("翻譯中沒有[{}]", orphan.id));
+    };
+
+    let field_types: Vec<FieldType> = match &orphan.field_type {
+        Some(field_type) => vec![field_type.clone()],
+        None => FieldType::ALL.to_vec(),
+    };
+    let fields: Vec<(FieldType, TextType, Vec<String>)> = field_types
+        .into_iter()
+        .filter_map(|field_type| {
+            let text_type = source.text_type_map.get(&field_type)?.clone();
+            let values = data.get_field(&field_type);
+            (!values.is_empty()).then(|| (field_type, text_type, values.clone()))
+        })
+        .collect();
+    if fields.is_empty() {
+        return Err(format!("[{}]的原文沒有可對應的欄位", target_id));
+    }
+    // 不覆蓋目標已經有的翻譯
+    for (field_type, _, _) in &fields {
+        let levels = manager
+            .translation_skills
+            .get(target_id)
+            .map_or(0, |data| data.get_field(field_type).len());
+        let translated = (0..levels).any(|level| {
+            manager.is_translated(&SegmentKey::new(target_id, field_type.clone(), level))
+        });
+        if translated {
+            return Err(format!(
+                "[{}]的{}已經有翻譯",
+                target_id,
+                field_type.to_str()
+            ));
+        }
+    }
+
+    let target = manager
+        .translation_skills
+        .entry(target_id.to_string())
+        .or_insert_with(|| SkillData {
+            id: target_id.to_string(),
+            ..Default::default()
+        });
+    let count = fields.len();
+    let moved: Vec<FieldType> = fields
+        .iter()
+        .map(|(field_type, _, _)| field_type.clone())
+        .collect();
+    for (field_type, text_type, values) in fields {
+        target.insert_data(text_type, field_type, values);
+    }
+    let remap = |key: &SegmentKey| remapped_key(key, &orphan.id, target_id, &moved);
+    move_entries(&mut manager.statuses, remap);
+    move_entries(&mut manager.review_flags, remap);
+    move_entries(&mut fingerprints.entries, remap);
+    for comment in comments.entries.iter_mut() {
+        if let Some(key) = remap(&comment.key) {
+            comment.key = key;
+        }
+    }
+    // 目標原文沒有的欄位留在原本的物件，之後可以再對應到其他物件或封存
+    for field_type in &moved {
+        take_translation(manager, &orphan.id, Some(field_type));
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{self, skill};
+    use crate::data::tooltip::{ReviewFlag, SegmentStatus};
+
+    fn manager() -> SkillManager {
+        let skill = |id, fields: &[(FieldType, &[&str])]| skill(id, TextType::SingleLine, fields);
+        test_util::manager(
+            vec![
+                skill("A000", &[(FieldType::Tip, &["마나"])]),
+                skill("A002", &[(FieldType::Ubertip, &["설명"])]),
+            ],
+            vec![
+                skill(
+                    "A000",
+                    &[(FieldType::Tip, &["魔力"]), (FieldType::Ubertip, &["說明"])],
+                ),
+                skill("A001", &[(FieldType::Ubertip, &["舊的說明"])]),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_find_and_take_orphans() {
+        let mut manager = manager();
+        let mut orphans = find_orphans(&manager);
+        let labels: Vec<String> = orphans.iter().map(|o| o.to_label()).collect();
+        assert_eq!(labels, vec!["[A000] Ubertip", "[A001] 整個物件"]);
+        assert_eq!(orphans[1].preview, "舊的說明");
+
+        orphans.iter_mut().for_each(|o| o.selected = true);
+        let taken = take_orphans(&mut manager, &orphans);
+        assert_eq!(taken["A000"].ubertip, vec!["說明".to_string()]);
+        assert!(taken["A000"].tip.is_empty());
+        assert_eq!(taken["A001"].ubertip, vec!["舊的說明".to_string()]);
+        assert!(find_orphans(&manager).is_empty());
+        assert_eq!(
+            manager.translation_skills["A000"].tip,
+            vec!["魔力".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_selected_orphans_keeps_translation() {
+        let manager = manager();
+        let mut orphans = find_orphans(&manager);
+        orphans[0].selected = true;
+        let selected = selected_orphans(&manager, &orphans);
+        assert_eq!(selected["A000"].ubertip, vec!["說明".to_string()]);
+        assert!(!selected.contains_key("A001"));
+        assert_eq!(find_orphans(&manager).len(), 2);
+    }
+
+    #[test]
+    fn test_remap_orphan() {
+        let mut manager = manager();
+        let mut fingerprints = SourceFingerprints::default();
+        let mut comments = Comments::default();
+        let old_key = SegmentKey::new("A001", FieldType::Ubertip, 0);
+        let new_key = SegmentKey::new("A002", FieldType::Ubertip, 0);
+        manager
+            .statuses
+            .insert(old_key.clone(), SegmentStatus::Approved);
+        fingerprints.record(&old_key, "설명");
+        comments.add(&old_key, "譯者", "名稱待確認", 0);
+
+        let mut orphan = find_orphans(&manager).remove(1);
+        orphan.target_id = "A003".to_string();
+        assert!(remap_orphan(&mut manager, &mut fingerprints, &mut comments, &orphan).is_err());
+
+        orphan.target_id = "A002".to_string();
+        assert_eq!(
+            remap_orphan(&mut manager, &mut fingerprints, &mut comments, &orphan),
+            Ok(1)
+        );
+        assert!(!manager.translation_skills.contains_key("A001"));
+        assert_eq!(
+            manager.translation_skills["A002"].ubertip,
+            vec!["舊的說明".to_string()]
+        );
+        assert_eq!(
+            manager.statuses.get(&new_key),
+            Some(&SegmentStatus::Approved)
+        );
+        assert!(!manager.statuses.contains_key(&old_key));
+        assert!(fingerprints.entries.contains_key(&new_key));
+        assert_eq!(comments.keys(false), vec![new_key]);
+    }
+
+    #[test]
+    fn test_remap_orphan_keeps_translated_target() {
+        let mut manager = manager();
+        manager.translation_skills.insert(
+            "A002".to_string(),
+            skill(
+                "A002",
+                TextType::SingleLine,
+                &[(FieldType::Ubertip, &["新的說明"])],
+            ),
+        );
+        let mut orphan = find_orphans(&manager).remove(1);
+        orphan.target_id = "A002".to_string();
+        let result = remap_orphan(
+            &mut manager,
+            &mut SourceFingerprints::default(),
+            &mut Comments::default(),
+            &orphan,
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            manager.translation_skills["A002"].ubertip,
+            vec!["新的說明".to_string()]
+        );
+        assert!(manager.translation_skills.contains_key("A001"));
+    }
+
+    #[test]
+    fn test_remap_orphan_keeps_unmoved_fields() {
+        let mut manager = manager();
+        manager.translation_skills.insert(
+            "A001".to_string(),
+            skill(
+                "A001",
+                TextType::SingleLine,
+                &[
+                    (FieldType::Tip, &["舊的名稱"]),
+                    (FieldType::Ubertip, &["舊的說明"]),
+                ],
+            ),
+        );
+        let tip_key = SegmentKey::new("A001", FieldType::Tip, 0);
+        manager
+            .statuses
+            .insert(tip_key.clone(), SegmentStatus::Approved);
+        manager
+            .review_flags
+            .insert(tip_key.clone(), ReviewFlag::Generated);
+
+        // A002的原文只有Ubertip，Tip要留在A001
+        let mut orphan = find_orphans(&manager).remove(1);
+        orphan.target_id = "A002".to_string();
+        let result = remap_orphan(
+            &mut manager,
+            &mut SourceFingerprints::default(),
+            &mut Comments::default(),
+            &orphan,
+        );
+        assert_eq!(result, Ok(1));
+        assert_eq!(
+            manager.translation_skills["A002"].ubertip,
+            vec!["舊的說明".to_string()]
+        );
+        assert_eq!(
+            manager.translation_skills["A001"].tip,
+            vec!["舊的名稱".to_string()]
+        );
+        assert!(manager.translation_skills["A001"].ubertip.is_empty());
+        assert_eq!(
+            manager.statuses.get(&tip_key),
+            Some(&SegmentStatus::Approved)
+        );
+        assert_eq!(
+            manager.review_flags.get(&tip_key),
+            Some(&ReviewFlag::Generated)
+        );
+        assert_eq!(find_orphans(&manager)[1].to_label(), "[A001] 整個物件");
+    }
+}
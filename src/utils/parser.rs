@@ -1,13 +1,23 @@
 use crate::data::tooltip::{SkillData, TooltipData};
+use crate::utils::comment::load_comments;
 use crate::utils::common::*;
+use crate::utils::fingerprint::load_fingerprints;
+use crate::utils::glossary::load_glossary;
+use crate::utils::qa::exception::load_exceptions;
+use crate::utils::qa::settings::load_settings;
+use crate::utils::review::load_review_flags;
+use crate::utils::status::load_statuses;
 use eframe::egui::TextBuffer;
 use regex::Regex;
 use std::collections::BTreeMap;
 use std::fs;
 use std::iter::Peekable;
+use std::path::Path;
 
-pub fn parse_tooltip_files() -> TooltipData {
+// 回傳讀取的資料與開啟時要顯示的警告
+pub fn parse_tooltip_files() -> (TooltipData, Vec<String>) {
     let mut data = TooltipData::default();
+    let mut warnings = vec![];
 
     // Parse source file
     let bytes = fs::read(SOURCE_FILE_NAME).expect("Failed to read source file");
@@ -19,12 +29,42 @@ pub fn parse_tooltip_files() -> TooltipData {
         fs::read_to_string(TRANSLATE_FILE_NAME).expect("Failed to read translation file");
     data.skill_manager.translation_skills = parse_content(&translation_content);
 
+    // 術語表不存在時使用空的術語表
+    data.glossary = load_optional(GLOSSARY_FILE_NAME, load_glossary, &mut warnings);
+    data.qa_exceptions = load_optional(QA_EXCEPTION_FILE_NAME, load_exceptions, &mut warnings);
+    data.qa_settings = load_optional(QA_SETTINGS_FILE_NAME, load_settings, &mut warnings);
+    data.fingerprints = load_optional(FINGERPRINT_FILE_NAME, load_fingerprints, &mut warnings);
+    data.skill_manager.review_flags =
+        load_optional(REVIEW_FLAG_FILE_NAME, load_review_flags, &mut warnings);
+    data.skill_manager.statuses = load_optional(STATUS_FILE_NAME, load_statuses, &mut warnings);
+    data.comments = load_optional(COMMENT_FILE_NAME, load_comments, &mut warnings);
+
     // Set first skill ID as current if any exists
     if let Some(first_id) = data.skill_manager.skills.keys().next() {
         data.current_id = first_id.clone();
     }
 
-    data
+    (data, warnings)
+}
+
+// 附屬檔案不存在時使用空的內容，存在卻無法讀取時加入警告
+fn load_optional<T: Default>(
+    path: &str,
+    load: impl Fn(&str) -> Result<T, String>,
+    warnings: &mut Vec<String>,
+) -> T {
+    if !Path::new(path).exists() {
+        return T::default();
+    }
+    load(path).unwrap_or_else(|e| {
+        warnings.push(format!("無法讀取{}", e));
+        T::default()
+    })
+}
+
+pub fn parse_file(path: &str) -> Result<BTreeMap<String, SkillData>, String> {
+    let bytes = fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+    Ok(parse_content(&String::from_utf8_lossy(&bytes)))
 }
 
 pub fn parse_content(content: &str) -> BTreeMap<String, SkillData> {
@@ -35,15 +75,20 @@ pub fn parse_content(content: &str) -> BTreeMap<String, SkillData> {
     let mut lines = content.lines().peekable();
     while let Some(line) = lines.next() {
         if let Some(id) = get_id(line) {
-            if is_available_skill_data(&current_data) {
-                entries.insert(current_id, current_data);
-                current_data = SkillData::default();
+            let data = std::mem::take(&mut current_data);
+            if is_available_skill_data(&data) {
+                entries.insert(current_id, data);
             }
 
             current_id = id;
             current_data.id = current_id.clone();
         }
 
+        if let Some((context_type, value)) = get_context(line) {
+            current_data.context.insert(context_type, value);
+            continue;
+        }
+
         if let Some(field_type) = get_field_type(line) {
             if let Some(text_type) = get_text_type(line, &mut lines) {
                 let field_value = match text_type {
@@ -0,0 +1,121 @@
+use crate::data::tooltip::{ReviewFlag, SegmentKey, SkillManager};
+use crate::utils::status::set_review_flag;
+use std::collections::HashMap;
+
+// 把單一翻譯套用到其他原文完全相同且尚未翻譯的段落
+pub fn propagate_segment(manager: &mut SkillManager, key: &SegmentKey) -> Vec<SegmentKey> {
+    if !manager.is_translated(key) {
+        return vec![];
+    }
+    let (Some(source), Some(translation)) = (manager.get_source(key), manager.get_translation(key))
+    else {
+        return vec![];
+    };
+
+    let mut translations = HashMap::new();
+    translations.insert(source.clone(), translation.clone());
+    fill_untranslated(manager, &translations)
+}
+
+// 以所有已翻譯段落填滿原文相同的未翻譯段落
+pub fn propagate_all(manager: &mut SkillManager) -> Vec<SegmentKey> {
+    let mut translations: HashMap<String, String> = HashMap::new();
+    for key in manager.segment_keys() {
+        // 自動套用的結果不再作為來源
+        if !manager.is_translated(&key) || manager.review_flags.contains_key(&key) {
+            continue;
+        }
+        if let (Some(source), Some(translation)) =
+            (manager.get_source(&key), manager.get_translation(&key))
+        {
+            translations
+                .entry(source.clone())
+                .or_insert_with(|| translation.clone());
+        }
+    }
+    fill_untranslated(manager, &translations)
+}
+
+fn fill_untranslated(
+    manager: &mut SkillManager,
+    translations: &HashMap<String, String>,
+) -> Vec<SegmentKey> {
+    let mut filled = vec![];
+    for key in manager.segment_keys() {
+        if manager.is_translated(&key) {
+            continue;
+        }
+        let Some(translation) = manager
+            .get_source(&key)
+            .and_then(|source| translations.get(source))
+        else {
+            continue;
+        };
+        if manager.set_translation(&key, translation.clone()) {
+            set_review_flag(manager, &key, ReviewFlag::AutoPropagated);
+            filled.push(key);
+        }
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{manager, tip_skill};
+    use crate::utils::common::FieldType;
+
+    #[test]
+    fn test_propagate_segment() {
+        let mut manager = manager(
+            vec![
+                tip_skill("A000", &["쿨다운", "레벨 2"]),
+                tip_skill("A001", &["레벨 1", "쿨다운"]),
+            ],
+            vec![tip_skill("A000", &["冷卻", "레벨 2"])],
+        );
+
+        let key = SegmentKey::new("A000", FieldType::Tip, 0);
+        let filled = propagate_segment(&mut manager, &key);
+        let expected = SegmentKey::new("A001", FieldType::Tip, 1);
+        assert_eq!(filled, vec![expected.clone()]);
+        assert_eq!(
+            manager.translation_skills["A001"].tip,
+            vec!["레벨 1", "冷卻"]
+        );
+        assert_eq!(
+            manager.review_flags.get(&expected),
+            Some(&ReviewFlag::AutoPropagated)
+        );
+
+        let untranslated = SegmentKey::new("A000", FieldType::Tip, 1);
+        assert!(propagate_segment(&mut manager, &untranslated).is_empty());
+    }
+
+    #[test]
+    fn test_propagate_all() {
+        let mut manager = manager(
+            vec![
+                tip_skill("A000", &["쿨다운", "레벨 1"]),
+                tip_skill("A001", &["레벨 1", "쿨다운"]),
+                tip_skill("A002", &["쿨다운"]),
+            ],
+            vec![
+                tip_skill("A000", &["冷卻", "레벨 1"]),
+                tip_skill("A001", &["等級 1", "쿨다운"]),
+            ],
+        );
+
+        let filled = propagate_all(&mut manager);
+        assert_eq!(filled.len(), 3);
+        assert_eq!(
+            manager.translation_skills["A000"].tip,
+            vec!["冷卻", "等級 1"]
+        );
+        assert_eq!(
+            manager.translation_skills["A001"].tip,
+            vec!["等級 1", "冷卻"]
+        );
+        assert_eq!(manager.translation_skills["A002"].tip, vec!["冷卻"]);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::data::tooltip::{SegmentKey, SkillManager};
+use crate::utils::glossary::Glossary;
+use crate::utils::qa::exception::QaExceptions;
+use crate::utils::qa::settings::QaSettings;
+use crate::utils::qa::{color, glossary, hotkey, length, level, numeric, residue, whitespace};
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "提示",
+            Severity::Warning => "警告",
+            Severity::Error => "錯誤",
+        }
+    }
+
+    // 給命令列與報表使用的英文代號
+    pub fn to_id(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+
+    pub fn from_id(s: &str) -> Option<Severity> {
+        [Severity::Info, Severity::Warning, Severity::Error]
+            .into_iter()
+            .find(|severity| severity.to_id() == s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QaRule {
+    Glossary,
+    Color,
+    Numeric,
+    Hotkey,
+    LevelCount,
+    Residue,
+    Length,
+    Whitespace,
+}
+
+impl QaRule {
+    pub const ALL: [QaRule; 8] = [
+        QaRule::Glossary,
+        QaRule::Color,
+        QaRule::Numeric,
+        QaRule::Hotkey,
+        QaRule::LevelCount,
+        QaRule::Residue,
+        QaRule::Length,
+        QaRule::Whitespace,
+    ];
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            QaRule::Glossary => "術語",
+            QaRule::Color => "色碼",
+            QaRule::Numeric => "數值",
+            QaRule::Hotkey => "快捷鍵",
+            QaRule::LevelCount => "等級數",
+            QaRule::Residue => "殘留原文",
+            QaRule::Length => "長度",
+            QaRule::Whitespace => "空白換行",
+        }
+    }
+
+    pub fn to_id(&self) -> &'static str {
+        match self {
+            QaRule::Glossary => "glossary",
+            QaRule::Color => "color",
+            QaRule::Numeric => "numeric",
+            QaRule::Hotkey => "hotkey",
+            QaRule::LevelCount => "level-count",
+            QaRule::Residue => "residue",
+            QaRule::Length => "length",
+            QaRule::Whitespace => "whitespace",
+        }
+    }
+
+    pub fn from_id(s: &str) -> Option<QaRule> {
+        QaRule::ALL.into_iter().find(|rule| rule.to_id() == s)
+    }
+
+    pub fn is_fixable(&self) -> bool {
+        matches!(self, QaRule::LevelCount | QaRule::Whitespace)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QaIssue {
+    pub key: SegmentKey,
+    pub rule: QaRule,
+    pub severity: Severity,
+    pub message: String,
+    // 翻譯文字中的位元組位置
+    pub range: Option<Range<usize>>,
+}
+
+impl QaIssue {
+    pub fn new(key: &SegmentKey, rule: QaRule, severity: Severity, message: String) -> Self {
+        Self {
+            key: key.clone(),
+            rule,
+            severity,
+            message,
+            range: None,
+        }
+    }
+
+    pub fn with_range(mut self, range: Range<usize>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    // 以字元為單位的位置，方便顯示
+    pub fn position(&self, translation: &str) -> Option<usize> {
+        let range = self.range.as_ref()?;
+        translation
+            .get(..range.start)
+            .map(|prefix| prefix.chars().count() + 1)
+    }
+}
+
+pub struct QaContext<'a> {
+    pub glossary: &'a Glossary,
+    pub exceptions: &'a QaExceptions,
+    pub settings: &'a QaSettings,
+}
+
+pub fn run_checks(manager: &SkillManager, context: &QaContext) -> Vec<QaIssue> {
+    let mut issues = vec![];
+    for key in manager.segment_keys() {
+        if !manager.is_translated(&key) {
+            continue;
+        }
+        let (Some(source), Some(translation)) =
+            (manager.get_source(&key), manager.get_translation(&key))
+        else {
+            continue;
+        };
+
+        issues.extend(glossary::check_glossary(
+            &key,
+            source,
+            translation,
+            context.glossary,
+        ));
+        issues.extend(color::check_color(&key, source, translation));
+        issues.extend(numeric::check_numeric(&key, source, translation));
+
+        let hotkey_field = manager
+            .skills
+            .get(&key.id)
+            .and_then(|data| data.context.get(&hotkey::hotkey_context(&key.field_type)));
+        issues.extend(hotkey::check_hotkey(
+            &key,
+            source,
+            translation,
+            hotkey_field.map(String::as_str),
+        ));
+
+        if let Some(profile) = context.settings.script_profile() {
+            issues.extend(residue::check_residue(
+                &key,
+                translation,
+                profile,
+                context.glossary,
+            ));
+        }
+        if let Some(budget) = context.settings.budget(&key.field_type) {
+            issues.extend(length::check_length(&key, translation, budget));
+        }
+        issues.extend(whitespace::check_whitespace(&key, source, translation));
+    }
+    issues.extend(level::check_levels(manager));
+    issues.retain(|issue| !context.exceptions.contains(issue));
+    issues
+}
+
+// 自動修正可以修正的問題
+pub fn apply_fix(manager: &mut SkillManager, issue: &QaIssue) -> bool {
+    match issue.rule {
+        QaRule::LevelCount => manager.sync_levels(&issue.key.id, &issue.key.field_type),
+        QaRule::Whitespace => {
+            let (Some(source), Some(translation)) = (
+                manager.get_source(&issue.key),
+                manager.get_translation(&issue.key),
+            ) else {
+                return false;
+            };
+            let fixed = whitespace::apply_whitespace(source, translation);
+            manager.set_translation(&issue.key, fixed)
+        }
+        _ => false,
+    }
+}
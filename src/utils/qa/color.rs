@@ -0,0 +1,160 @@
+use crate::data::tooltip::SegmentKey;
+use crate::utils::markup::*;
+use crate::utils::qa::check::{QaIssue, QaRule, Severity};
+use std::ops::Range;
+
+#[derive(Debug, PartialEq)]
+struct ColorRun {
+    hex: String,
+    range: Range<usize>,
+    terminated: bool,
+}
+
+#[derive(Debug, Default)]
+struct ColorSummary {
+    runs: Vec<ColorRun>,
+    invalid: Vec<Range<usize>>,
+    stray_resets: Vec<Range<usize>>,
+}
+
+fn summarize(text: &str) -> ColorSummary {
+    let mut summary = ColorSummary::default();
+    let mut is_open = false;
+
+    for span in tokenize_markup(text) {
+        match span.kind {
+            MarkupKind::Color(hex) => {
+                summary.runs.push(ColorRun {
+                    hex,
+                    range: span.range,
+                    terminated: false,
+                });
+                is_open = true;
+            }
+            MarkupKind::InvalidColor => summary.invalid.push(span.range),
+            MarkupKind::Reset => {
+                if is_open {
+                    if let Some(run) = summary.runs.last_mut() {
+                        run.terminated = true;
+                    }
+                    is_open = false;
+                } else {
+                    summary.stray_resets.push(span.range);
+                }
+            }
+            MarkupKind::Newline | MarkupKind::Text => {}
+        }
+    }
+
+    summary
+}
+
+pub fn check_color(key: &SegmentKey, source: &str, translation: &str) -> Vec<QaIssue> {
+    let source_summary = summarize(source);
+    let summary = summarize(translation);
+    let mut issues = vec![];
+
+    for range in &summary.invalid {
+        issues.push(
+            QaIssue::new(
+                key,
+                QaRule::Color,
+                Severity::Error,
+                format!("色碼格式錯誤「{}」", &translation[range.clone()]),
+            )
+            .with_range(range.clone()),
+        );
+    }
+
+    // 原文本身就有多餘的|r時，只在數量不同時提出
+    if summary.stray_resets.len() != source_summary.stray_resets.len() {
+        for range in &summary.stray_resets {
+            issues.push(
+                QaIssue::new(
+                    key,
+                    QaRule::Color,
+                    Severity::Warning,
+                    "多餘的|r，前面沒有對應的色碼".to_string(),
+                )
+                .with_range(range.clone()),
+            );
+        }
+    }
+
+    let source_hexes: Vec<&str> = source_summary.runs.iter().map(|r| r.hex.as_str()).collect();
+    let hexes: Vec<&str> = summary.runs.iter().map(|r| r.hex.as_str()).collect();
+    if source_hexes != hexes {
+        let index = source_hexes
+            .iter()
+            .zip(hexes.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let mut issue = QaIssue::new(
+            key,
+            QaRule::Color,
+            Severity::Warning,
+            format!(
+                "色碼與原文不同: 原文[{}] 翻譯[{}]",
+                source_hexes.join(", "),
+                hexes.join(", ")
+            ),
+        );
+        if let Some(run) = summary.runs.get(index) {
+            issue = issue.with_range(run.range.clone());
+        }
+        issues.push(issue);
+        return issues;
+    }
+
+    for (source_run, run) in source_summary.runs.iter().zip(summary.runs.iter()) {
+        if source_run.terminated && !run.terminated {
+            issues.push(
+                QaIssue::new(
+                    key,
+                    QaRule::Color,
+                    Severity::Warning,
+                    format!("色碼|c{}沒有以|r結束", run.hex),
+                )
+                .with_range(run.range.clone()),
+            );
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::common::FieldType;
+
+    fn key() -> SegmentKey {
+        SegmentKey::new("A000", FieldType::Tip, 0)
+    }
+
+    #[test]
+    fn test_check_color_valid() {
+        let source = "|c00ffff80천지를 가르는 개벽의 별|r(|cffffcc00R|r) - |r[|cffffcc00레벨 1|r]";
+        let translation = "|c00ffff80開天闢地之星|r(|cFFFFCC00R|r) - |r[|cffffcc00等級 1|r]";
+        assert!(check_color(&key(), source, translation).is_empty());
+    }
+
+    #[test]
+    fn test_check_color_errors() {
+        let source = "|cffffcc00레벨 1|r - 데미지";
+        let issues = check_color(&key(), source, "|cffffc00等級 1|r - 傷害");
+        assert_eq!(issues.len(), 3);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[0].range, Some(0..9));
+        assert_eq!(issues[1].range, Some(17..19));
+        assert_eq!(issues[2].range, None);
+
+        let issues = check_color(&key(), source, "|cffffcc00等級 1 - 傷害");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].range, Some(0..10));
+
+        let issues = check_color(&key(), source, "|cffffcc00等級 1|r - |c00ff8080傷害");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].range, Some(23..33));
+    }
+}
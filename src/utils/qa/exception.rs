@@ -0,0 +1,172 @@
+use crate::data::tooltip::SegmentKey;
+use crate::utils::common::*;
+use crate::utils::qa::check::QaIssue;
+use regex::Regex;
+use std::fs;
+
+pub const QA_EXCEPTION_ID_REGEX: &str = r#"^\[(.+)\]$"#;
+pub const QA_EXCEPTION_REGEX: &str = r#"^([A-Za-z]+)#(\d+)\s+(\S+)\s*=\s*"(.*)"$"#;
+
+// 使用者標記為刻意保留的QA結果，以段落、規則與訊息辨識
+#[derive(Debug, Clone, PartialEq)]
+pub struct QaException {
+    pub key: SegmentKey,
+    // 規則的英文代號，不受顯示名稱修改影響
+    pub rule: String,
+    pub message: String,
+}
+
+impl QaException {
+    pub fn from_issue(issue: &QaIssue) -> Self {
+        Self {
+            key: issue.key.clone(),
+            rule: issue.rule.to_id().to_string(),
+            message: issue.message.clone(),
+        }
+    }
+
+    pub fn matches(&self, issue: &QaIssue) -> bool {
+        self.key == issue.key && self.rule == issue.rule.to_id() && self.message == issue.message
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct QaExceptions {
+    pub entries: Vec<QaException>,
+}
+
+impl QaExceptions {
+    pub fn contains(&self, issue: &QaIssue) -> bool {
+        self.entries.iter().any(|e| e.matches(issue))
+    }
+
+    pub fn insert(&mut self, issue: &QaIssue) {
+        if !self.contains(issue) {
+            self.entries.push(QaException::from_issue(issue));
+        }
+    }
+}
+
+pub fn load_exceptions(path: &str) -> Result<QaExceptions, String> {
+    let content = read_lossy(path)?;
+    Ok(parse_exceptions(&content))
+}
+
+pub fn save_exceptions(path: &str, exceptions: &QaExceptions) -> Result<(), String> {
+    fs::write(path, output_exceptions(exceptions)).map_err(|e| e.to_string())
+}
+
+pub fn parse_exceptions(content: &str) -> QaExceptions {
+    let id_pattern = Regex::new(QA_EXCEPTION_ID_REGEX).unwrap();
+    let pattern = Regex::new(QA_EXCEPTION_REGEX).unwrap();
+
+    let mut exceptions = QaExceptions::default();
+    let mut current_id = None;
+    for line in content.lines() {
+        if let Some(caps) = id_pattern.captures(line) {
+            current_id = Some(caps[1].to_string());
+            continue;
+        }
+
+        let (Some(id), Some(caps)) = (&current_id, pattern.captures(line)) else {
+            continue;
+        };
+        let Some(field_type) = FieldType::from_str(&caps[1]) else {
+            continue;
+        };
+        let Ok(level) = caps[2].parse::<usize>() else {
+            continue;
+        };
+        exceptions.entries.push(QaException {
+            key: SegmentKey::new(id, field_type, level.saturating_sub(1)),
+            rule: caps[3].to_string(),
+            message: unescape_value(&caps[4]),
+        });
+    }
+
+    exceptions
+}
+
+pub fn output_exceptions(exceptions: &QaExceptions) -> String {
+    let mut entries: Vec<&QaException> = exceptions.entries.iter().collect();
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+    let mut output = String::new();
+    let mut current_id: Option<&str> = None;
+    for entry in entries {
+        if current_id != Some(entry.key.id.as_str()) {
+            if current_id.is_some() {
+                output.push(NEWLINE_SYMBOL);
+            }
+            output.push_line(&format!("[{}]", entry.key.id));
+            current_id = Some(entry.key.id.as_str());
+        }
+        output.push_line(&format!(
+            "{}#{} {} = \"{}\"",
+            entry.key.field_type.to_str(),
+            entry.key.level + 1,
+            entry.rule,
+            escape_value(&entry.message)
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::qa::check::{QaRule, Severity};
+
+    #[test]
+    fn test_parse_and_output_exceptions() {
+        let issue = QaIssue::new(
+            &SegmentKey::new("A000", FieldType::Ubertip, 1),
+            QaRule::Numeric,
+            Severity::Warning,
+            "翻譯中多出數值「2」".to_string(),
+        );
+        let mut exceptions = QaExceptions::default();
+        exceptions.insert(&issue);
+        exceptions.insert(&issue);
+        exceptions.entries.push(QaException {
+            key: SegmentKey::new("A001", FieldType::Tip, 0),
+            rule: "numeric".to_string(),
+            message: "翻譯中缺少原文的數值「0.5」".to_string(),
+        });
+
+        let content = output_exceptions(&exceptions);
+        assert_eq!(
+            content,
+            r#"[A000]
+Ubertip#2 numeric = "翻譯中多出數值「2」"
+
+[A001]
+Tip#1 numeric = "翻譯中缺少原文的數值「0.5」"
+"#
+        );
+
+        let parsed = parse_exceptions(&content);
+        assert_eq!(parsed.entries, exceptions.entries);
+        assert!(parsed.contains(&issue));
+    }
+
+    #[test]
+    fn test_exception_message_escape() {
+        // 訊息會引用翻譯內容，可能含有引號或換行
+        let mut exceptions = QaExceptions::default();
+        exceptions.entries.push(QaException {
+            key: SegmentKey::new("A000", FieldType::Ubertip, 0),
+            rule: "whitespace".to_string(),
+            message: "「\"火球\"」後面多出換行\n\\".to_string(),
+        });
+
+        let content = output_exceptions(&exceptions);
+        assert_eq!(
+            content,
+            r#"[A000]
+Ubertip#1 whitespace = "「\"火球\"」後面多出換行\n\\"
+"#
+        );
+        assert_eq!(parse_exceptions(&content).entries, exceptions.entries);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::data::tooltip::SegmentKey;
+use crate::utils::glossary::Glossary;
+use crate::utils::qa::check::{QaIssue, QaRule, Severity};
+
+pub fn check_glossary(
+    key: &SegmentKey,
+    source: &str,
+    translation: &str,
+    glossary: &Glossary,
+) -> Vec<QaIssue> {
+    let mut issues = vec![];
+    let mut checked: Vec<&str> = vec![];
+
+    for (_, entry) in glossary.find_terms(source) {
+        if checked.contains(&entry.term.as_str()) {
+            continue;
+        }
+        checked.push(entry.term.as_str());
+
+        if !entry.target.is_empty() && !translation.contains(entry.target.as_str()) {
+            issues.push(QaIssue::new(
+                key,
+                QaRule::Glossary,
+                Severity::Warning,
+                format!("術語「{}」應翻譯為「{}」", entry.term, entry.target),
+            ));
+        }
+
+        let forbidden = entry
+            .forbidden
+            .iter()
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty());
+        for variant in forbidden {
+            for (start, _) in translation.match_indices(variant) {
+                issues.push(
+                    QaIssue::new(
+                        key,
+                        QaRule::Glossary,
+                        Severity::Warning,
+                        format!(
+                            "術語「{}」不可翻譯為「{}」，請使用「{}」",
+                            entry.term, variant, entry.target
+                        ),
+                    )
+                    .with_range(start..start + variant.len()),
+                );
+            }
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::common::FieldType;
+    use crate::utils::glossary::GlossaryEntry;
+
+    #[test]
+    fn test_check_glossary() {
+        let glossary = Glossary {
+            entries: vec![GlossaryEntry {
+                term: "올스텟".to_string(),
+                target: "全屬性".to_string(),
+                notes: String::new(),
+                forbidden: vec!["全能力".to_string()],
+            }],
+        };
+        let key = SegmentKey::new("A000", FieldType::Tip, 0);
+
+        assert!(check_glossary(&key, "올스텟 3 증가", "全屬性增加3", &glossary).is_empty());
+        assert!(check_glossary(&key, "데미지 증가", "全能力增加3", &glossary).is_empty());
+
+        let issues = check_glossary(&key, "올스텟 3 증가, 올스텟", "全能力增加3", &glossary);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].range, None);
+        assert_eq!(issues[1].range, Some(0..9));
+        assert_eq!(issues[1].position("全能力增加3"), Some(1));
+    }
+}
@@ -0,0 +1,124 @@
+use crate::data::tooltip::SegmentKey;
+use crate::utils::common::*;
+use crate::utils::qa::check::{QaIssue, QaRule, Severity};
+use regex::Regex;
+use std::ops::Range;
+
+// (Q)、(|cffffcc00Q|r)，允許全形括號
+pub const HOTKEY_REGEX: &str =
+    r#"[(（]\s*(?:\|[cC][0-9a-fA-F]{8})?\s*([A-Za-z])\s*(?:\|[rR])?\s*[)）]"#;
+
+// 學習用的說明對應Researchhotkey，其餘對應Hotkey
+pub fn hotkey_context(field_type: &FieldType) -> ContextType {
+    match field_type {
+        FieldType::Researchtip | FieldType::Researchubertip => ContextType::Researchhotkey,
+        FieldType::Tip | FieldType::Ubertip => ContextType::Hotkey,
+    }
+}
+
+fn find_hotkeys(text: &str) -> Vec<(char, Range<usize>)> {
+    let pattern = Regex::new(HOTKEY_REGEX).unwrap();
+    pattern
+        .captures_iter(text)
+        .filter_map(|caps| {
+            let letter = caps.get(1)?;
+            let c = letter.as_str().chars().next()?.to_ascii_uppercase();
+            Some((c, letter.range()))
+        })
+        .collect()
+}
+
+pub fn check_hotkey(
+    key: &SegmentKey,
+    source: &str,
+    translation: &str,
+    hotkey: Option<&str>,
+) -> Vec<QaIssue> {
+    let source_hotkeys: Vec<char> = find_hotkeys(source).into_iter().map(|(c, _)| c).collect();
+    let hotkeys = find_hotkeys(translation);
+    let letters: Vec<char> = hotkeys.iter().map(|(c, _)| *c).collect();
+
+    if source_hotkeys != letters {
+        let index = source_hotkeys
+            .iter()
+            .zip(letters.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let format_letters = |letters: &[char]| {
+            letters
+                .iter()
+                .map(|c| format!("({})", c))
+                .collect::<String>()
+        };
+        let message = if letters.is_empty() {
+            format!("翻譯中缺少快捷鍵{}", format_letters(&source_hotkeys))
+        } else {
+            format!(
+                "快捷鍵與原文不同: 原文{} 翻譯{}",
+                format_letters(&source_hotkeys),
+                format_letters(&letters)
+            )
+        };
+        let mut issue = QaIssue::new(key, QaRule::Hotkey, Severity::Warning, message);
+        if let Some((_, range)) = hotkeys.get(index) {
+            issue = issue.with_range(range.clone());
+        }
+        return vec![issue];
+    }
+
+    let Some(hotkey) = hotkey.and_then(|h| h.trim().chars().next()) else {
+        return vec![];
+    };
+    let hotkey = hotkey.to_ascii_uppercase();
+    hotkeys
+        .into_iter()
+        .filter(|(c, _)| *c != hotkey)
+        .map(|(c, range)| {
+            QaIssue::new(
+                key,
+                QaRule::Hotkey,
+                Severity::Warning,
+                format!(
+                    "快捷鍵({})與{}欄位的({})不同",
+                    c,
+                    hotkey_context(&key.field_type).to_str(),
+                    hotkey
+                ),
+            )
+            .with_range(range)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> SegmentKey {
+        SegmentKey::new("A000", FieldType::Tip, 0)
+    }
+
+    #[test]
+    fn test_find_hotkeys() {
+        let hotkeys = find_hotkeys("|c00ffff80무공|r(|cffffcc00q|r) (Az03) （W） (2倍)");
+        assert_eq!(hotkeys, vec![('Q', 29..30), ('W', 44..45)]);
+    }
+
+    #[test]
+    fn test_check_hotkey() {
+        let source = "|c00ffff80외경은 있지만 괴로움은 없고|r(E)";
+        assert!(check_hotkey(&key(), source, "|c00ffff80敬畏而無苦|r(E)", Some("E")).is_empty());
+
+        let issues = check_hotkey(&key(), source, "敬畏而無苦(|cffffcc00Q|r)", Some("E"));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].message, "快捷鍵與原文不同: 原文(E) 翻譯(Q)");
+        assert_eq!(issues[0].range, Some(26..27));
+
+        let issues = check_hotkey(&key(), source, "敬畏而無苦", Some("E"));
+        assert_eq!(issues[0].message, "翻譯中缺少快捷鍵(E)");
+
+        let issues = check_hotkey(&key(), "외경(R)", "敬畏(R)", Some("E"));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].message, "快捷鍵(R)與Hotkey欄位的(E)不同");
+    }
+}
@@ -0,0 +1,83 @@
+use crate::data::tooltip::SegmentKey;
+use crate::utils::markup::{display_lines, DisplayLine};
+use crate::utils::qa::check::{QaIssue, QaRule, Severity};
+use crate::utils::qa::settings::LineBudget;
+
+// 超過寬度上限的行
+pub fn overflow_lines(text: &str, budget: &LineBudget) -> Vec<DisplayLine> {
+    if budget.max_width == 0 {
+        return vec![];
+    }
+    display_lines(text)
+        .into_iter()
+        .filter(|line| line.width > budget.max_width)
+        .collect()
+}
+
+pub fn check_length(key: &SegmentKey, translation: &str, budget: &LineBudget) -> Vec<QaIssue> {
+    let mut issues = vec![];
+
+    let lines = display_lines(translation);
+    if budget.max_lines > 0 && lines.len() > budget.max_lines {
+        let extra_start = lines[budget.max_lines].range.start;
+        issues.push(
+            QaIssue::new(
+                key,
+                QaRule::Length,
+                Severity::Warning,
+                format!("共{}行，超過上限{}行", lines.len(), budget.max_lines),
+            )
+            .with_range(extra_start..translation.len()),
+        );
+    }
+
+    for (index, line) in lines.iter().enumerate() {
+        if budget.max_width > 0 && line.width > budget.max_width {
+            issues.push(
+                QaIssue::new(
+                    key,
+                    QaRule::Length,
+                    Severity::Warning,
+                    format!(
+                        "第{}行寬度{}，超過上限{}",
+                        index + 1,
+                        line.width,
+                        budget.max_width
+                    ),
+                )
+                .with_range(line.range.clone()),
+            );
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::common::FieldType;
+
+    #[test]
+    fn test_check_length() {
+        let key = SegmentKey::new("A000", FieldType::Ubertip, 0);
+        let budget = LineBudget {
+            field_type: FieldType::Ubertip,
+            max_lines: 2,
+            max_width: 10,
+        };
+        assert!(check_length(&key, "|cffffcc00等級 1|r|n傷害 400", &budget).is_empty());
+
+        let translation = "第一行\n每秒造成大量傷害|n第三行";
+        let issues = check_length(&key, translation, &budget);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].message, "共3行，超過上限2行");
+        assert_eq!(&translation[issues[0].range.clone().unwrap()], "第三行");
+        assert_eq!(issues[1].message, "第2行寬度16，超過上限10");
+        assert_eq!(
+            &translation[issues[1].range.clone().unwrap()],
+            "每秒造成大量傷害"
+        );
+        assert_eq!(overflow_lines(translation, &budget).len(), 1);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::data::tooltip::{SegmentKey, SkillManager};
+use crate::utils::common::FieldType;
+use crate::utils::qa::check::{QaIssue, QaRule, Severity};
+
+// 以欄位為單位比對等級數，段落位置指向第一個只存在於一邊的等級
+pub fn check_levels(manager: &SkillManager) -> Vec<QaIssue> {
+    let mut issues = vec![];
+    for id in manager.translation_skills.keys() {
+        for field_type in FieldType::ALL.iter() {
+            let Some((source, translation)) = manager.level_count_mismatch(id, field_type) else {
+                continue;
+            };
+            let key = SegmentKey::new(id, field_type.clone(), source.min(translation));
+            let message = if source > translation {
+                format!("原文有{}個等級，翻譯只有{}個等級", source, translation)
+            } else {
+                format!("原文只有{}個等級，翻譯有{}個等級", source, translation)
+            };
+            issues.push(QaIssue::new(
+                &key,
+                QaRule::LevelCount,
+                Severity::Warning,
+                message,
+            ));
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{self, skill};
+    use crate::data::tooltip::ReviewFlag;
+    use crate::utils::common::TextType;
+    use crate::utils::status::set_review_flag;
+
+    #[test]
+    fn test_check_and_sync_levels() {
+        let mut manager = test_util::manager(
+            vec![
+                skill(
+                    "A000",
+                    TextType::SingleLineArray,
+                    &[(FieldType::Tip, &["레벨 1", "레벨 2", "레벨 3"])],
+                ),
+                skill(
+                    "A001",
+                    TextType::SingleLine,
+                    &[(FieldType::Tip, &["레벨 1"])],
+                ),
+            ],
+            vec![
+                skill(
+                    "A000",
+                    TextType::SingleLineArray,
+                    &[(FieldType::Tip, &["等級 1"])],
+                ),
+                skill(
+                    "A001",
+                    TextType::SingleLineArray,
+                    &[(FieldType::Tip, &["等級 1", "等級 2"])],
+                ),
+            ],
+        );
+        let extra = SegmentKey::new("A001", FieldType::Tip, 1);
+        set_review_flag(&mut manager, &extra, ReviewFlag::AutoPropagated);
+
+        let issues = check_levels(&manager);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].key, SegmentKey::new("A000", FieldType::Tip, 1));
+        assert_eq!(issues[0].message, "原文有3個等級，翻譯只有1個等級");
+        assert_eq!(issues[1].key, extra);
+
+        assert!(manager.sync_levels("A000", &FieldType::Tip));
+        assert_eq!(
+            manager.translation_skills["A000"].tip,
+            vec!["等級 1", "레벨 2", "레벨 3"]
+        );
+        assert!(manager.sync_levels("A001", &FieldType::Tip));
+        assert_eq!(manager.translation_skills["A001"].tip, vec!["等級 1"]);
+        assert_eq!(
+            manager.translation_skills["A001"].text_type_map[&FieldType::Tip],
+            TextType::SingleLine
+        );
+        assert!(manager.review_flags.is_empty());
+        assert!(!manager.sync_levels("A001", &FieldType::Tip));
+        assert!(check_levels(&manager).is_empty());
+    }
+}
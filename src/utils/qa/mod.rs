@@ -0,0 +1,12 @@
+pub mod check;
+pub mod color;
+pub mod exception;
+pub mod glossary;
+pub mod hotkey;
+pub mod length;
+pub mod level;
+pub mod numeric;
+pub mod report;
+pub mod residue;
+pub mod settings;
+pub mod whitespace;
\ No newline at end of file
@@ -0,0 +1,272 @@
+use crate::data::tooltip::SegmentKey;
+use crate::utils::markup::*;
+use crate::utils::qa::check::{QaIssue, QaRule, Severity};
+use std::ops::Range;
+
+const NUMBER_EPSILON: f64 = 1e-9;
+
+#[derive(Debug, PartialEq, Clone)]
+struct NumberToken {
+    // 在原字串中的位元組位置
+    range: Range<usize>,
+    // 可能的數值，例如 1,000 可能是一千也可能是小數的 1.000
+    values: Vec<f64>,
+}
+
+impl NumberToken {
+    fn matches(&self, other: &NumberToken) -> bool {
+        self.values
+            .iter()
+            .any(|a| other.values.iter().any(|b| (a - b).abs() < NUMBER_EPSILON))
+    }
+}
+
+// 全形數字與符號轉為半形
+fn normalize_char(c: char) -> char {
+    match c {
+        '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c),
+        '．' => '.',
+        '，' => ',',
+        '\u{00A0}' | '\u{202F}' => ' ',
+        _ => c,
+    }
+}
+
+fn is_digit(c: char) -> bool {
+    normalize_char(c).is_ascii_digit()
+}
+
+fn is_separator(c: char) -> bool {
+    matches!(normalize_char(c), '.' | ',' | ' ' | '\'')
+}
+
+// 以指定的小數點解讀數字，其餘分隔符號必須是三位一組的千分位
+fn parse_with_decimal(text: &str, decimal: char) -> Option<f64> {
+    let (integer, fraction) = match text.rfind(decimal) {
+        Some(index) => (&text[..index], Some(&text[index + 1..])),
+        None => (text, None),
+    };
+    if integer.contains(decimal) {
+        return None;
+    }
+
+    let groups: Vec<&str> = integer
+        .split(|c: char| c != decimal && is_separator(c))
+        .collect();
+    if groups.len() > 1
+        && (groups[0].is_empty()
+            || groups[0].len() > 3
+            || groups[1..].iter().any(|group| group.len() != 3))
+    {
+        return None;
+    }
+
+    let mut number = groups.concat();
+    if let Some(fraction) = fraction {
+        number.push('.');
+        number.push_str(fraction);
+    }
+    number.parse().ok()
+}
+
+fn interpret(text: &str) -> Vec<f64> {
+    let mut values: Vec<f64> = vec![];
+    for decimal in ['.', ','] {
+        if let Some(value) = parse_with_decimal(text, decimal) {
+            if !values.iter().any(|v| (v - value).abs() < NUMBER_EPSILON) {
+                values.push(value);
+            }
+        }
+    }
+    values
+}
+
+fn push_numbers(numbers: &mut Vec<NumberToken>, text: &str, offset: usize) {
+    let normalized: String = text.chars().map(normalize_char).collect();
+    let values = interpret(&normalized);
+    if !values.is_empty() {
+        numbers.push(NumberToken {
+            range: offset..offset + text.len(),
+            values,
+        });
+        return;
+    }
+
+    // 無法解讀成單一數字時(例如 15,50,100)，拆成個別數字
+    let mut start = None;
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (is_digit(c), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                let part: String = text[s..i].chars().map(normalize_char).collect();
+                if let Ok(value) = part.parse() {
+                    numbers.push(NumberToken {
+                        range: offset + s..offset + i,
+                        values: vec![value],
+                    });
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn scan_text(numbers: &mut Vec<NumberToken>, text: &str, offset: usize) {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let (start, c) = chars[i];
+        // 前面接英文字母的數字視為代號(例如 A011)
+        let after_letter = i > 0 && chars[i - 1].1.is_ascii_alphabetic();
+        if !is_digit(c) || after_letter {
+            i += 1;
+            while after_letter && i < chars.len() && is_digit(chars[i].1) {
+                i += 1;
+            }
+            continue;
+        }
+
+        let mut end = i;
+        while end < chars.len() && is_digit(chars[end].1) {
+            end += 1;
+        }
+        while end + 1 < chars.len() && is_separator(chars[end].1) && is_digit(chars[end + 1].1) {
+            let mut next = end + 1;
+            while next < chars.len() && is_digit(chars[next].1) {
+                next += 1;
+            }
+            // 空白作為千分位時必須剛好三位數
+            if matches!(normalize_char(chars[end].1), ' ' | '\'') && next - end - 1 != 3 {
+                break;
+            }
+            end = next;
+        }
+
+        let end_byte = chars.get(end).map_or(text.len(), |&(index, _)| index);
+        push_numbers(numbers, &text[start..end_byte], offset + start);
+        i = end;
+    }
+}
+
+fn extract(text: &str) -> Vec<NumberToken> {
+    let mut numbers = vec![];
+    for span in tokenize_markup(text) {
+        if span.kind == MarkupKind::Text {
+            scan_text(&mut numbers, &text[span.range.clone()], span.range.start);
+        }
+    }
+    numbers
+}
+
+pub fn check_numeric(key: &SegmentKey, source: &str, translation: &str) -> Vec<QaIssue> {
+    let source_numbers = extract(source);
+    let numbers = extract(translation);
+
+    let mut matched = vec![false; numbers.len()];
+    let mut missing: Vec<&NumberToken> = vec![];
+    for source_number in &source_numbers {
+        let found = numbers
+            .iter()
+            .enumerate()
+            .position(|(i, number)| !matched[i] && number.matches(source_number));
+        match found {
+            Some(i) => matched[i] = true,
+            None => missing.push(source_number),
+        }
+    }
+    let extra: Vec<&NumberToken> = numbers
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !matched)
+        .map(|(number, _)| number)
+        .collect();
+
+    let mut issues = vec![];
+    // 缺少與多出的數值依序配對，視為數值被修改
+    let changed = missing.len().min(extra.len());
+    for (source_number, number) in missing.iter().zip(extra.iter()) {
+        issues.push(
+            QaIssue::new(
+                key,
+                QaRule::Numeric,
+                Severity::Warning,
+                format!(
+                    "原文的數值「{}」在翻譯中變為「{}」",
+                    &source[source_number.range.clone()],
+                    &translation[number.range.clone()]
+                ),
+            )
+            .with_range(number.range.clone()),
+        );
+    }
+    for source_number in &missing[changed..] {
+        issues.push(QaIssue::new(
+            key,
+            QaRule::Numeric,
+            Severity::Warning,
+            format!(
+                "翻譯中缺少原文的數值「{}」",
+                &source[source_number.range.clone()]
+            ),
+        ));
+    }
+    for number in &extra[changed..] {
+        issues.push(
+            QaIssue::new(
+                key,
+                QaRule::Numeric,
+                Severity::Warning,
+                format!("翻譯中多出數值「{}」", &translation[number.range.clone()]),
+            )
+            .with_range(number.range.clone()),
+        );
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::common::FieldType;
+
+    fn key() -> SegmentKey {
+        SegmentKey::new("A000", FieldType::Ubertip, 0)
+    }
+
+    #[test]
+    fn test_extract_numbers() {
+        let text = "|cffffcc00레벨 1|r - 0.5초당 15, 1,000의 A011 １，２００ 50% 3 000";
+        let numbers: Vec<Vec<f64>> = extract(text).into_iter().map(|n| n.values).collect();
+        assert_eq!(
+            numbers,
+            vec![
+                vec![1.0],
+                vec![0.5],
+                vec![15.0],
+                vec![1000.0, 1.0],
+                vec![1200.0, 1.2],
+                vec![50.0],
+                vec![3000.0],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_numeric() {
+        let source = "쿨다운 : 45초, 데미지 : 1000, 0.5초당 15";
+        let translation = "冷卻 : 45秒, 傷害 : 1,000, 每0,5秒 15";
+        assert!(check_numeric(&key(), source, translation).is_empty());
+
+        let issues = check_numeric(&key(), source, "冷卻 : 45秒, 傷害 : 1350, 每秒 15");
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].message, "原文的數值「1000」在翻譯中變為「1350」");
+        assert_eq!(issues[0].range, Some(25..29));
+        assert_eq!(issues[1].message, "翻譯中缺少原文的數值「0.5」");
+
+        let issues = check_numeric(&key(), "데미지 : 1000", "傷害 : 1000 (2倍)");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].message, "翻譯中多出數值「2」");
+    }
+}
@@ -0,0 +1,266 @@
+use crate::data::tooltip::{SegmentKey, SkillManager};
+use crate::utils::common::*;
+use crate::utils::qa::check::{QaIssue, Severity};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Text,
+    Json,
+    Junit,
+}
+
+impl ReportFormat {
+    pub fn from_id(s: &str) -> Option<ReportFormat> {
+        match s {
+            "text" => Some(ReportFormat::Text),
+            "json" => Some(ReportFormat::Json),
+            "junit" => Some(ReportFormat::Junit),
+            _ => None,
+        }
+    }
+}
+
+// 達到門檻的嚴重程度時回傳true，門檻為None時永遠不算失敗
+pub fn exceeds_threshold(issues: &[QaIssue], threshold: Option<Severity>) -> bool {
+    threshold.is_some_and(|threshold| issues.iter().any(|issue| issue.severity >= threshold))
+}
+
+pub fn format_report(
+    format: ReportFormat,
+    manager: &SkillManager,
+    issues: &[QaIssue],
+    threshold: Option<Severity>,
+) -> String {
+    match format {
+        ReportFormat::Text => format_text(manager, issues),
+        ReportFormat::Json => format_json(manager, issues),
+        ReportFormat::Junit => format_junit(manager, issues, threshold),
+    }
+}
+
+fn count(issues: &[QaIssue], severity: Severity) -> usize {
+    issues.iter().filter(|i| i.severity == severity).count()
+}
+
+fn position(manager: &SkillManager, issue: &QaIssue) -> Option<usize> {
+    manager
+        .get_translation(&issue.key)
+        .and_then(|translation| issue.position(translation))
+}
+
+fn format_text(manager: &SkillManager, issues: &[QaIssue]) -> String {
+    let mut output = String::new();
+    for issue in issues {
+        let position = position(manager, issue)
+            .map(|p| format!(" 第{}字", p))
+            .unwrap_or_default();
+        output.push_line(&format!(
+            "[{}] {} {}{}: {}",
+            issue.severity.to_str(),
+            issue.rule.to_str(),
+            issue.key.to_label(),
+            position,
+            issue.message
+        ));
+    }
+    output.push_line(&format!(
+        "共{}個問題(錯誤{} 警告{} 提示{})",
+        issues.len(),
+        count(issues, Severity::Error),
+        count(issues, Severity::Warning),
+        count(issues, Severity::Info)
+    ));
+    output
+}
+
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn format_json(manager: &SkillManager, issues: &[QaIssue]) -> String {
+    let mut output = String::new();
+    output.push_line("{");
+    output.push_line(&format!(
+        "  \"summary\": {{\"error\": {}, \"warning\": {}, \"info\": {}}},",
+        count(issues, Severity::Error),
+        count(issues, Severity::Warning),
+        count(issues, Severity::Info)
+    ));
+    output.push_line("  \"issues\": [");
+    for (index, issue) in issues.iter().enumerate() {
+        let position = position(manager, issue)
+            .map(|p| p.to_string())
+            .unwrap_or("null".to_string());
+        let separator = if index + 1 < issues.len() { "," } else { "" };
+        output.push_line(&format!(
+            "    {{\"id\": \"{}\", \"field\": \"{}\", \"level\": {}, \"rule\": \"{}\", \"severity\": \"{}\", \"position\": {}, \"message\": \"{}\"}}{}",
+            escape_json(&issue.key.id),
+            issue.key.field_type.to_str(),
+            issue.key.level + 1,
+            issue.rule.to_id(),
+            issue.severity.to_id(),
+            position,
+            escape_json(&issue.message),
+            separator
+        ));
+    }
+    output.push_line("  ]");
+    output.push_line("}");
+    output
+}
+
+// 每個檢查過的段落是一個testcase，達到門檻的問題才算失敗
+fn format_junit(manager: &SkillManager, issues: &[QaIssue], threshold: Option<Severity>) -> String {
+    let mut cases: BTreeMap<SegmentKey, Vec<&QaIssue>> = manager
+        .segment_keys()
+        .into_iter()
+        .filter(|key| manager.is_translated(key))
+        .map(|key| (key, vec![]))
+        .collect();
+    for issue in issues {
+        cases.entry(issue.key.clone()).or_default().push(issue);
+    }
+
+    let is_failure = |issue: &&QaIssue| threshold.is_some_and(|t| issue.severity >= t);
+    let failures = cases
+        .values()
+        .filter(|issues| issues.iter().any(is_failure))
+        .count();
+
+    let mut output = String::new();
+    output.push_line(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    output.push_line(&format!(
+        r#"<testsuites name="wts-qa" tests="{}" failures="{}">"#,
+        cases.len(),
+        failures
+    ));
+    output.push_line(&format!(
+        r#"  <testsuite name="wts-qa" tests="{}" failures="{}">"#,
+        cases.len(),
+        failures
+    ));
+    for (key, issues) in &cases {
+        let name = format!("{} #{}", key.field_type.to_str(), key.level + 1);
+        let lines: Vec<String> = issues
+            .iter()
+            .map(|issue| {
+                format!(
+                    "[{}] {}: {}",
+                    issue.severity.to_id(),
+                    issue.rule.to_id(),
+                    issue.message
+                )
+            })
+            .collect();
+        let failed: Vec<&&QaIssue> = issues.iter().filter(|issue| is_failure(issue)).collect();
+
+        if lines.is_empty() {
+            output.push_line(&format!(
+                r#"    <testcase classname="{}" name="{}"/>"#,
+                escape_xml(&key.id),
+                name
+            ));
+            continue;
+        }
+        output.push_line(&format!(
+            r#"    <testcase classname="{}" name="{}">"#,
+            escape_xml(&key.id),
+            name
+        ));
+        match failed.first() {
+            Some(first) => output.push_line(&format!(
+                r#"      <failure message="{}" type="{}">{}</failure>"#,
+                escape_xml(&first.message),
+                first.rule.to_id(),
+                escape_xml(&lines.join("\n"))
+            )),
+            None => output.push_line(&format!(
+                "      <system-out>{}</system-out>",
+                escape_xml(&lines.join("\n"))
+            )),
+        }
+        output.push_line("    </testcase>");
+    }
+    output.push_line("  </testsuite>");
+    output.push_line("</testsuites>");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{self, tip_skill};
+    use crate::utils::qa::check::QaRule;
+
+    fn manager() -> SkillManager {
+        test_util::manager(
+            vec![tip_skill("A000", &["데미지 1000", "데미지 2000"])],
+            vec![tip_skill("A000", &["傷害 \"1350\"", "傷害 2000"])],
+        )
+    }
+
+    fn issues() -> Vec<QaIssue> {
+        vec![QaIssue::new(
+            &SegmentKey::new("A000", FieldType::Tip, 0),
+            QaRule::Numeric,
+            Severity::Warning,
+            "原文的數值「1000」在翻譯中變為「1350」".to_string(),
+        )
+        .with_range(8..12)]
+    }
+
+    #[test]
+    fn test_threshold() {
+        assert!(exceeds_threshold(&issues(), Some(Severity::Warning)));
+        assert!(!exceeds_threshold(&issues(), Some(Severity::Error)));
+        assert!(!exceeds_threshold(&issues(), None));
+    }
+
+    #[test]
+    fn test_format_report() {
+        let manager = manager();
+        let issues = issues();
+
+        let text = format_report(ReportFormat::Text, &manager, &issues, None);
+        assert!(text.starts_with(
+            "[警告] 數值 [A000] Tip #1 第5字: 原文的數值「1000」在翻譯中變為「1350」\n"
+        ));
+
+        let json = format_report(ReportFormat::Json, &manager, &issues, None);
+        assert!(json.contains(r#""summary": {"error": 0, "warning": 1, "info": 0}"#));
+        assert!(json.contains(r#"{"id": "A000", "field": "Tip", "level": 1, "rule": "numeric", "severity": "warning", "position": 5, "#));
+
+        let junit = format_report(
+            ReportFormat::Junit,
+            &manager,
+            &issues,
+            Some(Severity::Warning),
+        );
+        assert!(junit.contains(r#"<testsuite name="wts-qa" tests="2" failures="1">"#));
+        assert!(junit.contains(
+            r#"<failure message="原文的數值「1000」在翻譯中變為「1350」" type="numeric">"#
+        ));
+
+        let junit = format_report(
+            ReportFormat::Junit,
+            &manager,
+            &issues,
+            Some(Severity::Error),
+        );
+        assert!(junit.contains(r#"failures="0""#));
+        assert!(junit.contains("<system-out>[warning] numeric: "));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::data::tooltip::SegmentKey;
+use crate::utils::glossary::Glossary;
+use crate::utils::markup::*;
+use crate::utils::qa::check::{QaIssue, QaRule, Severity};
+use crate::utils::qa::settings::ScriptProfile;
+use crate::utils::script::{script_of, Script};
+use std::ops::Range;
+
+// 術語表中指定的翻譯允許保留原文文字，例如保留韓文的專有名詞
+fn glossary_ranges(translation: &str, glossary: &Glossary) -> Vec<Range<usize>> {
+    glossary
+        .entries
+        .iter()
+        .filter(|entry| !entry.target.is_empty())
+        .flat_map(|entry| {
+            translation
+                .match_indices(entry.target.as_str())
+                .map(|(start, target)| start..start + target.len())
+        })
+        .collect()
+}
+
+// 依文字分類的字元，略過色碼與術語
+fn script_chars(translation: &str, glossary: &Glossary) -> Vec<(usize, char, Option<Script>)> {
+    let ignored = glossary_ranges(translation, glossary);
+    tokenize_markup(translation)
+        .into_iter()
+        .filter(|span| span.kind == MarkupKind::Text)
+        .flat_map(|span| {
+            translation[span.range.clone()]
+                .char_indices()
+                .map(move |(i, c)| (span.range.start + i, c))
+        })
+        .filter(|(i, _)| !ignored.iter().any(|range| range.contains(i)))
+        .map(|(i, c)| (i, c, script_of(c)))
+        .collect()
+}
+
+// 連續的禁止文字(中間只隔空白)合併為一段，不跨越色碼與換行
+fn forbidden_runs(
+    chars: &[(usize, char, Option<Script>)],
+    forbidden: &[Script],
+) -> Vec<(Script, Range<usize>)> {
+    let mut runs: Vec<(Script, Range<usize>)> = vec![];
+    let mut last_index = None;
+    for (index, &(i, c, script)) in chars.iter().enumerate() {
+        let Some(script) = script.filter(|s| forbidden.contains(s)) else {
+            continue;
+        };
+        let end = i + c.len_utf8();
+        let continues = last_index.is_some_and(|last: usize| {
+            let gap = &chars[last..=index];
+            gap[1..gap.len() - 1]
+                .iter()
+                .all(|(_, c, _)| c.is_whitespace() && *c != '\n')
+                && gap
+                    .windows(2)
+                    .all(|pair| pair[0].0 + pair[0].1.len_utf8() == pair[1].0)
+        });
+        match runs.last_mut() {
+            Some((run_script, range)) if continues && *run_script == script => range.end = end,
+            _ => runs.push((script, i..end)),
+        }
+        last_index = Some(index);
+    }
+    runs
+}
+
+pub fn check_residue(
+    key: &SegmentKey,
+    translation: &str,
+    profile: &ScriptProfile,
+    glossary: &Glossary,
+) -> Vec<QaIssue> {
+    let chars = script_chars(translation, glossary);
+    let mut issues: Vec<QaIssue> = forbidden_runs(&chars, &profile.forbidden)
+        .into_iter()
+        .map(|(script, range)| {
+            QaIssue::new(
+                key,
+                QaRule::Residue,
+                Severity::Warning,
+                format!(
+                    "翻譯中殘留{}「{}」",
+                    script.to_label(),
+                    &translation[range.clone()]
+                ),
+            )
+            .with_range(range)
+        })
+        .collect();
+
+    let has_letters = chars.iter().any(|(_, _, script)| script.is_some());
+    let has_expected = chars
+        .iter()
+        .any(|(_, _, script)| script.is_some_and(|s| profile.expected.contains(&s)));
+    if !profile.expected.is_empty() && has_letters && !has_expected {
+        let labels: Vec<&str> = profile.expected.iter().map(|s| s.to_label()).collect();
+        issues.push(QaIssue::new(
+            key,
+            QaRule::Residue,
+            Severity::Warning,
+            format!("翻譯中沒有{}，可能尚未翻譯", labels.join("或")),
+        ));
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::common::FieldType;
+    use crate::utils::glossary::GlossaryEntry;
+
+    fn key() -> SegmentKey {
+        SegmentKey::new("A000", FieldType::Tip, 0)
+    }
+
+    fn profile(language: &str) -> ScriptProfile {
+        crate::utils::qa::settings::QaSettings::default()
+            .script_profiles
+            .into_iter()
+            .find(|p| p.language == language)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_check_residue() {
+        let glossary = Glossary {
+            entries: vec![GlossaryEntry {
+                term: "하산".to_string(),
+                target: "하산".to_string(),
+                ..Default::default()
+            }],
+        };
+        let zh = profile("zh-TW");
+        let translation = "|cffffcc00等級 1|r - 하산의 400 데미지 주기";
+        let issues = check_residue(&key(), translation, &zh, &glossary);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].message, "翻譯中殘留韓文「의」");
+        assert_eq!(issues[1].message, "翻譯中殘留韓文「데미지 주기」");
+        assert_eq!(
+            &translation[issues[1].range.clone().unwrap()],
+            "데미지 주기"
+        );
+
+        let issues = check_residue(&key(), "400의 데미지", &profile("en"), &glossary);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[1].message, "翻譯中沒有拉丁字母，可能尚未翻譯");
+
+        let issues = check_residue(&key(), "每級 능력\n|cffffcc00레벨|r", &zh, &glossary);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[1].message, "翻譯中殘留韓文「레벨」");
+
+        assert!(check_residue(&key(), "|c00ffff80造成 400 傷害|r", &zh, &glossary).is_empty());
+    }
+}
@@ -0,0 +1,262 @@
+use crate::utils::common::*;
+use crate::utils::script::Script;
+use regex::Regex;
+use std::fs;
+
+pub const QA_SECTION_REGEX: &str = r#"^\[(.+)\]$"#;
+pub const QA_NUMBER_REGEX: &str = r#"^[A-Za-z]+\s*=\s*(\d+)$"#;
+pub const DEFAULT_LANGUAGE: &str = "zh-TW";
+
+const LANGUAGE_SECTION: &str = "Language";
+const SCRIPT_SECTION_PREFIX: &str = "Script.";
+const BUDGET_SECTION_PREFIX: &str = "Budget.";
+
+// 目標語言應該使用與不應該出現的文字
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptProfile {
+    pub language: String,
+    pub expected: Vec<Script>,
+    pub forbidden: Vec<Script>,
+}
+
+impl ScriptProfile {
+    fn new(language: &str, expected: &[Script], forbidden: &[Script]) -> Self {
+        Self {
+            language: language.to_string(),
+            expected: expected.to_vec(),
+            forbidden: forbidden.to_vec(),
+        }
+    }
+}
+
+// 欄位的行數與每行寬度上限，0表示不限制
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineBudget {
+    pub field_type: FieldType,
+    pub max_lines: usize,
+    pub max_width: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QaSettings {
+    pub language: String,
+    pub script_profiles: Vec<ScriptProfile>,
+    pub budgets: Vec<LineBudget>,
+}
+
+impl Default for QaSettings {
+    fn default() -> Self {
+        use Script::*;
+        Self {
+            language: DEFAULT_LANGUAGE.to_string(),
+            script_profiles: vec![
+                ScriptProfile::new("zh-TW", &[Han], &[Hangul, Kana]),
+                ScriptProfile::new("zh-CN", &[Han], &[Hangul, Kana]),
+                ScriptProfile::new("ja", &[Kana, Han], &[Hangul]),
+                ScriptProfile::new("en", &[Latin], &[Hangul, Han, Kana]),
+            ],
+            budgets: FieldType::ALL
+                .iter()
+                .map(|field_type| LineBudget {
+                    field_type: field_type.clone(),
+                    max_lines: match field_type {
+                        FieldType::Researchtip | FieldType::Tip => 1,
+                        FieldType::Researchubertip | FieldType::Ubertip => 20,
+                    },
+                    max_width: 60,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl QaSettings {
+    pub fn script_profile(&self) -> Option<&ScriptProfile> {
+        self.script_profiles
+            .iter()
+            .find(|profile| profile.language == self.language)
+    }
+
+    pub fn budget(&self, field_type: &FieldType) -> Option<&LineBudget> {
+        self.budgets.iter().find(|b| &b.field_type == field_type)
+    }
+}
+
+pub fn load_settings(path: &str) -> Result<QaSettings, String> {
+    let content = read_lossy(path)?;
+    Ok(parse_settings(&content))
+}
+
+pub fn save_settings(path: &str, settings: &QaSettings) -> Result<(), String> {
+    fs::write(path, output_settings(settings)).map_err(|e| e.to_string())
+}
+
+pub fn parse_settings(content: &str) -> QaSettings {
+    let section_pattern = Regex::new(QA_SECTION_REGEX).unwrap();
+    let value_pattern = Regex::new(SINGLE_LINE_REGEX).unwrap();
+    let array_pattern = Regex::new(SINGLE_LINE_ARRAY_REGEX).unwrap();
+    let number_pattern = Regex::new(QA_NUMBER_REGEX).unwrap();
+
+    let mut settings = QaSettings::default();
+    // 檔案中有設定的語言才覆蓋預設值
+    let mut profiles: Vec<ScriptProfile> = vec![];
+    let mut section = String::new();
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        if let Some(caps) = section_pattern.captures(line) {
+            section = caps[1].to_string();
+            if let Some(language) = section.strip_prefix(SCRIPT_SECTION_PREFIX) {
+                profiles.push(ScriptProfile::new(language, &[], &[]));
+            }
+            continue;
+        }
+
+        if let Some(field_type) = section
+            .strip_prefix(BUDGET_SECTION_PREFIX)
+            .and_then(FieldType::from_str)
+        {
+            let Some(caps) = number_pattern.captures(line) else {
+                continue;
+            };
+            let value = caps[1].parse().unwrap_or_default();
+            // 預設值已包含所有欄位
+            let Some(budget) = settings
+                .budgets
+                .iter_mut()
+                .find(|b| b.field_type == field_type)
+            else {
+                continue;
+            };
+            match line.split_once('=').map(|(key, _)| key.trim()) {
+                Some("MaxLines") => budget.max_lines = value,
+                Some("MaxWidth") => budget.max_width = value,
+                _ => {}
+            }
+            continue;
+        }
+
+        let Some((key, _)) = line.split_once('=') else {
+            continue;
+        };
+        match (section.as_str(), key.trim()) {
+            (LANGUAGE_SECTION, "Target") => {
+                if let Some(caps) = value_pattern.captures(line) {
+                    settings.language = caps[1].to_string();
+                }
+            }
+            (_, "Expected" | "Forbidden") if section.starts_with(SCRIPT_SECTION_PREFIX) => {
+                let scripts: Vec<Script> = lines
+                    .by_ref()
+                    .take_while(|line| *line != "}")
+                    .filter_map(|line| array_pattern.captures(line))
+                    .filter_map(|caps| Script::from_str(&caps[1]))
+                    .collect();
+                if let Some(profile) = profiles.last_mut() {
+                    if key.trim() == "Expected" {
+                        profile.expected = scripts;
+                    } else {
+                        profile.forbidden = scripts;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for profile in profiles {
+        match settings
+            .script_profiles
+            .iter_mut()
+            .find(|p| p.language == profile.language)
+        {
+            Some(existing) => *existing = profile,
+            None => settings.script_profiles.push(profile),
+        }
+    }
+    settings
+}
+
+fn push_scripts(output: &mut String, name: &str, scripts: &[Script]) {
+    output.push_line(&format!("{} = {{", name));
+    for script in scripts {
+        output.push_line(&format!("\"{}\",", script.to_str()));
+    }
+    output.push_line("}");
+}
+
+pub fn output_settings(settings: &QaSettings) -> String {
+    let mut output = String::new();
+    output.push_line(&format!("[{}]", LANGUAGE_SECTION));
+    output.push_line(&format!("Target = \"{}\"", settings.language));
+
+    for profile in &settings.script_profiles {
+        output.push(NEWLINE_SYMBOL);
+        output.push_line(&format!("[{}{}]", SCRIPT_SECTION_PREFIX, profile.language));
+        push_scripts(&mut output, "Expected", &profile.expected);
+        push_scripts(&mut output, "Forbidden", &profile.forbidden);
+    }
+
+    for budget in &settings.budgets {
+        output.push(NEWLINE_SYMBOL);
+        output.push_line(&format!(
+            "[{}{}]",
+            BUDGET_SECTION_PREFIX,
+            budget.field_type.to_str()
+        ));
+        output.push_line(&format!("MaxLines = {}", budget.max_lines));
+        output.push_line(&format!("MaxWidth = {}", budget.max_width));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_output_settings() {
+        let content = r#"[Language]
+Target = "en"
+
+[Script.en]
+Expected = {
+"Latin",
+}
+Forbidden = {
+"Hangul",
+"Cyrillic",
+}
+
+[Script.ru]
+Expected = {
+"Cyrillic",
+}
+Forbidden = {
+}
+
+[Budget.Ubertip]
+MaxLines = 12
+MaxWidth = 0
+"#;
+        let settings = parse_settings(content);
+        assert_eq!(settings.language, "en");
+        assert_eq!(
+            settings.script_profile(),
+            Some(&ScriptProfile::new(
+                "en",
+                &[Script::Latin],
+                &[Script::Hangul, Script::Cyrillic]
+            ))
+        );
+        assert_eq!(settings.script_profiles.len(), 5);
+        assert_eq!(
+            settings.script_profiles[0],
+            QaSettings::default().script_profiles[0]
+        );
+        let budget = settings.budget(&FieldType::Ubertip).unwrap();
+        assert_eq!((budget.max_lines, budget.max_width), (12, 0));
+        let budget = settings.budget(&FieldType::Tip).unwrap();
+        assert_eq!((budget.max_lines, budget.max_width), (1, 60));
+        assert_eq!(parse_settings(&output_settings(&settings)), settings);
+    }
+}
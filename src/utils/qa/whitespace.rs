@@ -0,0 +1,284 @@
+use crate::data::tooltip::SegmentKey;
+use crate::utils::qa::check::{QaIssue, QaRule, Severity};
+use std::ops::Range;
+
+// 以換行或|n切開的一行，前後空白分開保存
+#[derive(Debug, PartialEq)]
+struct RawLine<'a> {
+    lead: &'a str,
+    content: &'a str,
+    trail: &'a str,
+    start: usize,
+}
+
+impl RawLine<'_> {
+    fn is_blank(&self) -> bool {
+        self.content.is_empty()
+    }
+}
+
+// 回傳每一行與行之間的分隔符號
+fn split_lines(text: &str) -> (Vec<RawLine<'_>>, Vec<&str>) {
+    let bytes = text.as_bytes();
+    let mut lines = vec![];
+    let mut separators = vec![];
+    let mut start = 0;
+    let mut i = 0;
+    while i <= bytes.len() {
+        let separator_len = match bytes.get(i) {
+            None => 0,
+            Some(b'\n') => 1,
+            Some(b'|') if matches!(bytes.get(i + 1), Some(b'n' | b'N')) => 2,
+            Some(_) => {
+                i += 1;
+                continue;
+            }
+        };
+
+        let line = &text[start..i];
+        let content = line.trim();
+        let lead_len = line.len() - line.trim_start().len();
+        lines.push(RawLine {
+            lead: &line[..lead_len],
+            content,
+            trail: &line[lead_len + content.len()..],
+            start,
+        });
+        if separator_len == 0 {
+            break;
+        }
+        separators.push(&text[i..i + separator_len]);
+        i += separator_len;
+        start = i;
+    }
+    (lines, separators)
+}
+
+fn leading(text: &str) -> &str {
+    &text[..text.len() - text.trim_start().len()]
+}
+
+fn trailing(text: &str) -> &str {
+    &text[text.trim_end().len()..]
+}
+
+fn describe(whitespace: &str) -> String {
+    if whitespace.is_empty() {
+        return "無".to_string();
+    }
+    whitespace
+        .chars()
+        .map(|c| match c {
+            ' ' => "空格".to_string(),
+            '\n' => "換行".to_string(),
+            '\t' => "Tab".to_string(),
+            c => format!("{:?}", c),
+        })
+        .collect::<Vec<String>>()
+        .join("+")
+}
+
+fn blank_lines(lines: &[RawLine]) -> Vec<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.is_blank())
+        .map(|(index, _)| index + 1)
+        .collect()
+}
+
+fn new_issue(key: &SegmentKey, message: String, range: Range<usize>) -> QaIssue {
+    let issue = QaIssue::new(key, QaRule::Whitespace, Severity::Warning, message);
+    if range.is_empty() {
+        issue
+    } else {
+        issue.with_range(range)
+    }
+}
+
+pub fn check_whitespace(key: &SegmentKey, source: &str, translation: &str) -> Vec<QaIssue> {
+    let mut issues = vec![];
+
+    let (source_lead, lead) = (leading(source), leading(translation));
+    if source_lead != lead {
+        issues.push(new_issue(
+            key,
+            format!(
+                "開頭空白與原文不同: 原文[{}] 翻譯[{}]",
+                describe(source_lead),
+                describe(lead)
+            ),
+            0..lead.len(),
+        ));
+    }
+    let (source_trail, trail) = (trailing(source), trailing(translation));
+    if source_trail != trail {
+        issues.push(new_issue(
+            key,
+            format!(
+                "結尾空白與原文不同: 原文[{}] 翻譯[{}]",
+                describe(source_trail),
+                describe(trail)
+            ),
+            translation.len() - trail.len()..translation.len(),
+        ));
+    }
+
+    let (source_lines, source_separators) = split_lines(source);
+    let (lines, separators) = split_lines(translation);
+
+    let count_newline = |separators: &[&str]| separators.iter().filter(|s| s.len() == 2).count();
+    let (source_count, count) = (
+        count_newline(&source_separators),
+        count_newline(&separators),
+    );
+    if source_count != count {
+        issues.push(new_issue(
+            key,
+            format!("|n的數量與原文不同: 原文{}個 翻譯{}個", source_count, count),
+            0..0,
+        ));
+    }
+
+    let (source_blanks, blanks) = (blank_lines(&source_lines), blank_lines(&lines));
+    if source_blanks != blanks {
+        let format_lines = |blanks: &[usize]| match blanks.len() {
+            0 => "無".to_string(),
+            _ => blanks
+                .iter()
+                .map(|line| format!("第{}行", line))
+                .collect::<Vec<String>>()
+                .join("、"),
+        };
+        issues.push(new_issue(
+            key,
+            format!(
+                "空行與原文不同: 原文[{}] 翻譯[{}]",
+                format_lines(&source_blanks),
+                format_lines(&blanks)
+            ),
+            0..0,
+        ));
+        return issues;
+    }
+
+    // 行結構相同時逐行比對縮排，整段的開頭與結尾已在上面檢查過
+    let last = lines.len().saturating_sub(1);
+    for (index, (source_line, line)) in source_lines.iter().zip(lines.iter()).enumerate() {
+        if line.is_blank() {
+            continue;
+        }
+        if index > 0 && source_line.lead != line.lead {
+            issues.push(new_issue(
+                key,
+                format!(
+                    "第{}行開頭空白與原文不同: 原文[{}] 翻譯[{}]",
+                    index + 1,
+                    describe(source_line.lead),
+                    describe(line.lead)
+                ),
+                line.start..line.start + line.lead.len(),
+            ));
+        }
+        if index < last && source_line.trail != line.trail {
+            let end = line.start + line.lead.len() + line.content.len();
+            issues.push(new_issue(
+                key,
+                format!(
+                    "第{}行結尾空白與原文不同: 原文[{}] 翻譯[{}]",
+                    index + 1,
+                    describe(source_line.trail),
+                    describe(line.trail)
+                ),
+                end..end + line.trail.len(),
+            ));
+        }
+    }
+
+    issues
+}
+
+// 把原文的空白與換行結構套用到翻譯上，非空行數不同時只修正整段開頭與結尾
+pub fn apply_whitespace(source: &str, translation: &str) -> String {
+    let (source_lines, source_separators) = split_lines(source);
+    let (lines, _) = split_lines(translation);
+    let contents: Vec<&str> = lines
+        .iter()
+        .filter(|line| !line.is_blank())
+        .map(|line| line.content)
+        .collect();
+    let source_count = source_lines.iter().filter(|line| !line.is_blank()).count();
+
+    if contents.len() != source_count {
+        return format!(
+            "{}{}{}",
+            leading(source),
+            translation.trim(),
+            trailing(source)
+        );
+    }
+
+    let mut contents = contents.into_iter();
+    let mut result = String::new();
+    for (index, source_line) in source_lines.iter().enumerate() {
+        if index > 0 {
+            result.push_str(source_separators[index - 1]);
+        }
+        result.push_str(source_line.lead);
+        if !source_line.is_blank() {
+            result.push_str(contents.next().unwrap_or_default());
+        }
+        result.push_str(source_line.trail);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::common::FieldType;
+
+    fn key() -> SegmentKey {
+        SegmentKey::new("A000", FieldType::Ubertip, 0)
+    }
+
+    #[test]
+    fn test_check_whitespace() {
+        let source = "\n|c00ff8080\n ※레벨당 능력|n\n |cffffcc00레벨 1|r - 400 ";
+        let translation = "\n|c00ff8080\n ※每級能力|n\n |cffffcc00等級 1|r - 400 ";
+        assert!(check_whitespace(&key(), source, translation).is_empty());
+
+        let translation = "|c00ff8080\n※每級能力\n |cffffcc00等級 1|r - 400";
+        let messages: Vec<String> = check_whitespace(&key(), source, translation)
+            .into_iter()
+            .map(|issue| issue.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "開頭空白與原文不同: 原文[換行] 翻譯[無]",
+                "結尾空白與原文不同: 原文[空格] 翻譯[無]",
+                "|n的數量與原文不同: 原文1個 翻譯0個",
+                "空行與原文不同: 原文[第1行、第4行] 翻譯[無]",
+            ]
+        );
+
+        let issues = check_whitespace(&key(), "a\n b", "甲\nb");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].message,
+            "第2行開頭空白與原文不同: 原文[空格] 翻譯[無]"
+        );
+    }
+
+    #[test]
+    fn test_apply_whitespace() {
+        let source = "\n|c00ff8080\n ※레벨당 능력|n\n |cffffcc00레벨 1|r - 400 ";
+        let translation = "|c00ff8080\n※每級能力\n |cffffcc00等級 1|r - 400";
+        assert_eq!(
+            apply_whitespace(source, translation),
+            "\n|c00ff8080\n ※每級能力|n\n |cffffcc00等級 1|r - 400 "
+        );
+        assert_eq!(apply_whitespace(" a|nb ", "甲"), " 甲 ");
+    }
+}
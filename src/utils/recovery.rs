@@ -0,0 +1,222 @@
+use crate::data::tooltip::{ReviewFlag, SegmentKey, SegmentStatus, SkillData, SkillManager};
+use crate::utils::common::*;
+use crate::utils::export::output_translated;
+use crate::utils::parser::parse_content;
+use crate::utils::review::{load_review_flags, output_review_flags};
+use crate::utils::status::{
+    is_translated_text, load_statuses, output_statuses, replace_translations, segment_status,
+};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// 未存檔的翻譯與各段落的標記、審校狀態，標記與狀態寫在另外的復原檔
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Journal {
+    pub skills: BTreeMap<String, SkillData>,
+    // 舊版本留下的復原檔沒有標記與狀態，讀入時為None
+    pub review_flags: Option<BTreeMap<SegmentKey, ReviewFlag>>,
+    pub statuses: Option<BTreeMap<SegmentKey, SegmentStatus>>,
+}
+
+impl Journal {
+    pub fn take(manager: &SkillManager) -> Self {
+        Self {
+            skills: manager.translation_skills.clone(),
+            review_flags: Some(manager.review_flags.clone()),
+            statuses: Some(manager.statuses.clone()),
+        }
+    }
+
+    // 還原到翻譯中，沒有記錄狀態時保留目前的狀態，翻譯改變的段落與使用者修改一樣調整狀態
+    pub fn restore(self, manager: &mut SkillManager) {
+        if let Some(review_flags) = self.review_flags {
+            manager.review_flags = review_flags;
+        }
+        match self.statuses {
+            Some(statuses) => {
+                manager.translation_skills = self.skills;
+                manager.statuses = statuses;
+            }
+            None => replace_translations(manager, self.skills),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecoveredChange {
+    pub key: SegmentKey,
+    // 存檔中的翻譯，None代表存檔中沒有這個段落
+    pub saved: Option<String>,
+    pub recovered: Option<String>,
+    pub saved_status: SegmentStatus,
+    pub recovered_status: SegmentStatus,
+}
+
+// 以與translation.ini相同的格式寫入復原檔
+pub fn write_journal(journal: &Journal) -> Result<(), String> {
+    let write = |path: &str, output: String| {
+        fs::write(path, output).map_err(|e| format!("{}: {}", path, e))
+    };
+    write(RECOVERY_FILE_NAME, output_translated(&journal.skills)?)?;
+    if let Some(review_flags) = &journal.review_flags {
+        write(
+            RECOVERY_REVIEW_FLAG_FILE_NAME,
+            output_review_flags(review_flags),
+        )?;
+    }
+    if let Some(statuses) = &journal.statuses {
+        write(RECOVERY_STATUS_FILE_NAME, output_statuses(statuses))?;
+    }
+    Ok(())
+}
+
+pub fn load_journal() -> Option<Journal> {
+    if !Path::new(RECOVERY_FILE_NAME).exists() {
+        return None;
+    }
+    let bytes = fs::read(RECOVERY_FILE_NAME).ok()?;
+    Some(Journal {
+        skills: parse_content(&String::from_utf8_lossy(&bytes)),
+        review_flags: load_review_flags(RECOVERY_REVIEW_FLAG_FILE_NAME).ok(),
+        statuses: load_statuses(RECOVERY_STATUS_FILE_NAME).ok(),
+    })
+}
+
+pub fn remove_journal() -> Result<(), String> {
+    for path in [
+        RECOVERY_FILE_NAME,
+        RECOVERY_REVIEW_FLAG_FILE_NAME,
+        RECOVERY_STATUS_FILE_NAME,
+    ] {
+        if Path::new(path).exists() {
+            fs::remove_file(path).map_err(|e| format!("{}: {}", path, e))?;
+        }
+    }
+    Ok(())
+}
+
+// 列出復原檔與存檔的翻譯、標記或狀態不同的段落
+pub fn recovered_changes(manager: &SkillManager, journal: &Journal) -> Vec<RecoveredChange> {
+    let get = |skills: &BTreeMap<String, SkillData>, key: &SegmentKey| {
+        skills
+            .get(&key.id)?
+            .get_field(&key.field_type)
+            .get(key.level)
+            .cloned()
+    };
+    let review_flags = journal
+        .review_flags
+        .as_ref()
+        .unwrap_or(&manager.review_flags);
+    let statuses = journal.statuses.as_ref().unwrap_or(&manager.statuses);
+    let status = |text: &Option<String>,
+                  review_flags: &BTreeMap<SegmentKey, ReviewFlag>,
+                  statuses: &BTreeMap<SegmentKey, SegmentStatus>,
+                  key: &SegmentKey| {
+        let source = manager
+            .get_source(key)
+            .map(|s| s.as_str())
+            .unwrap_or_default();
+        let status = statuses.get(key);
+        let translated = is_translated_text(source, text.as_deref().unwrap_or_default(), status);
+        segment_status(translated, status, review_flags.get(key))
+    };
+    manager
+        .segment_keys()
+        .into_iter()
+        .filter_map(|key| {
+            let saved = get(&manager.translation_skills, &key);
+            let recovered = get(&journal.skills, &key);
+            let unchanged = saved == recovered
+                && manager.review_flags.get(&key) == review_flags.get(&key)
+                && manager.statuses.get(&key) == statuses.get(&key);
+            if unchanged {
+                return None;
+            }
+            Some(RecoveredChange {
+                saved_status: status(&saved, &manager.review_flags, &manager.statuses, &key),
+                recovered_status: status(&recovered, review_flags, statuses, &key),
+                key,
+                saved,
+                recovered,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{manager, skill_map, tip_skill};
+    use crate::utils::common::FieldType;
+
+    #[test]
+    fn test_recovered_changes() {
+        let manager = manager(
+            vec![tip_skill("A000", &["레벨 1", "레벨 2", "레벨 3"])],
+            vec![tip_skill("A000", &["等級 1", "레벨 2"])],
+        );
+
+        let journal = output_translated(&skill_map(vec![tip_skill(
+            "A000",
+            &["等級 1", "等級 2", "等級 3"],
+        )]))
+        .unwrap();
+        let journal = Journal {
+            skills: parse_content(&journal),
+            ..Default::default()
+        };
+        let changes = recovered_changes(&manager, &journal);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].key, SegmentKey::new("A000", FieldType::Tip, 1));
+        assert_eq!(changes[0].saved.as_deref(), Some("레벨 2"));
+        assert_eq!(changes[0].recovered.as_deref(), Some("等級 2"));
+        assert_eq!(changes[1].saved, None);
+
+        assert!(recovered_changes(&manager, &Journal::take(&manager)).is_empty());
+    }
+
+    #[test]
+    fn test_recover_statuses() {
+        let mut manager = manager(
+            vec![tip_skill("A000", &["레벨 1", "레벨 2"])],
+            vec![tip_skill("A000", &["等級 1", "等級 2"])],
+        );
+        let first = SegmentKey::new("A000", FieldType::Tip, 0);
+        let second = SegmentKey::new("A000", FieldType::Tip, 1);
+        manager
+            .statuses
+            .insert(first.clone(), SegmentStatus::Approved);
+
+        // 只改變狀態與標記也要能還原
+        let mut journal = Journal::take(&manager);
+        let statuses = journal.statuses.as_mut().unwrap();
+        statuses.insert(first.clone(), SegmentStatus::Reviewed);
+        statuses.insert(second.clone(), SegmentStatus::MachineFilled);
+        let review_flags = journal.review_flags.as_mut().unwrap();
+        review_flags.insert(second.clone(), ReviewFlag::AutoPropagated);
+        let changes = recovered_changes(&manager, &journal);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].saved_status, SegmentStatus::Approved);
+        assert_eq!(changes[0].recovered_status, SegmentStatus::Reviewed);
+        assert_eq!(changes[1].recovered_status, SegmentStatus::MachineFilled);
+
+        journal.clone().restore(&mut manager);
+        assert_eq!(manager.statuses.get(&first), Some(&SegmentStatus::Reviewed));
+        assert_eq!(
+            manager.review_flags.get(&second),
+            Some(&ReviewFlag::AutoPropagated)
+        );
+        assert!(recovered_changes(&manager, &journal).is_empty());
+
+        // 沒有狀態的舊復原檔保留目前的狀態
+        let journal = Journal {
+            skills: manager.translation_skills.clone(),
+            ..Default::default()
+        };
+        assert!(recovered_changes(&manager, &journal).is_empty());
+        journal.restore(&mut manager);
+        assert_eq!(manager.statuses.get(&first), Some(&SegmentStatus::Reviewed));
+    }
+}
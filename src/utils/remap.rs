@@ -0,0 +1,241 @@
+use crate::data::tooltip::{SkillData, SkillManager};
+use crate::utils::common::*;
+use crate::utils::import::ReferenceData;
+use crate::utils::similarity::similarity;
+
+pub const LEGACY_SOURCE_FILE_NAME: &str = "legacy_source.ini";
+pub const REMAP_THRESHOLD: f32 = 0.5;
+pub const REMAP_AUTO_SELECT_THRESHOLD: f32 = 0.8;
+
+const NAME_WEIGHT: f32 = 0.35;
+const PARENT_WEIGHT: f32 = 0.15;
+const HOTKEY_WEIGHT: f32 = 0.15;
+const TEXT_WEIGHT: f32 = 0.35;
+
+#[derive(Debug, Clone)]
+pub struct RemapProposal {
+    pub old_id: String,
+    pub new_id: String,
+    pub score: f32,
+    pub name_score: Option<f32>,
+    pub parent_match: Option<bool>,
+    pub hotkey_match: Option<bool>,
+    pub text_score: f32,
+    pub selected: bool,
+}
+
+pub fn score_pair(old: &SkillData, new: &SkillData) -> RemapProposal {
+    let context_pair = |context_type: &ContextType| match (
+        old.context.get(context_type),
+        new.context.get(context_type),
+    ) {
+        (Some(a), Some(b)) => Some((a, b)),
+        _ => None,
+    };
+
+    let name_score = context_pair(&ContextType::Name).map(|(a, b)| similarity(a, b));
+    let parent_match = context_pair(&ContextType::Parent).map(|(a, b)| a == b);
+    let hotkey_match = context_pair(&ContextType::Hotkey).map(|(a, b)| a.eq_ignore_ascii_case(b));
+    let text_score = similarity(&joined_text(old), &joined_text(new));
+
+    // 只計算兩邊都有的欄位
+    let mut total = TEXT_WEIGHT * text_score;
+    let mut weight = TEXT_WEIGHT;
+    if let Some(score) = name_score {
+        total += NAME_WEIGHT * score;
+        weight += NAME_WEIGHT;
+    }
+    if let Some(matched) = parent_match {
+        total += PARENT_WEIGHT * f32::from(u8::from(matched));
+        weight += PARENT_WEIGHT;
+    }
+    if let Some(matched) = hotkey_match {
+        total += HOTKEY_WEIGHT * f32::from(u8::from(matched));
+        weight += HOTKEY_WEIGHT;
+    }
+
+    let score = total / weight;
+    RemapProposal {
+        old_id: old.id.clone(),
+        new_id: new.id.clone(),
+        score,
+        name_score,
+        parent_match,
+        hotkey_match,
+        text_score,
+        selected: score >= REMAP_AUTO_SELECT_THRESHOLD,
+    }
+}
+
+fn joined_text(data: &SkillData) -> String {
+    FieldType::ALL
+        .iter()
+        .flat_map(|field_type| data.get_field(field_type).iter())
+        .map(|s| s.as_str())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+pub fn propose_remaps(manager: &SkillManager, legacy: &ReferenceData) -> Vec<RemapProposal> {
+    // 舊ID仍存在且原文未變動的不需要搬移
+    let old_skills: Vec<&SkillData> = legacy
+        .translation_skills
+        .keys()
+        .filter_map(|id| legacy.skills.get(id))
+        .filter(|old| {
+            manager.skills.get(&old.id).is_none_or(|current| {
+                FieldType::ALL
+                    .iter()
+                    .any(|f| current.get_field(f) != old.get_field(f))
+            })
+        })
+        .collect();
+
+    let new_skills: Vec<&SkillData> = manager
+        .skills
+        .values()
+        .filter(|new| !manager.translation_skills.contains_key(&new.id))
+        .collect();
+
+    let mut pairs: Vec<RemapProposal> = old_skills
+        .iter()
+        .flat_map(|old| new_skills.iter().map(|new| score_pair(old, new)))
+        .filter(|proposal| proposal.score >= REMAP_THRESHOLD && proposal.old_id != proposal.new_id)
+        .collect();
+    pairs.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    // 以分數高低一對一配對
+    let mut result: Vec<RemapProposal> = vec![];
+    for proposal in pairs {
+        if result
+            .iter()
+            .any(|p| p.old_id == proposal.old_id || p.new_id == proposal.new_id)
+        {
+            continue;
+        }
+        result.push(proposal);
+    }
+    result.sort_by(|a, b| a.old_id.cmp(&b.old_id));
+    result
+}
+
+pub fn apply_remaps(
+    manager: &mut SkillManager,
+    legacy: &ReferenceData,
+    proposals: &[RemapProposal],
+) -> usize {
+    let mut count = 0;
+    for proposal in proposals.iter().filter(|p| p.selected) {
+        let Some(source) = manager.skills.get(&proposal.new_id) else {
+            continue;
+        };
+        let Some(old) = legacy.translation_skills.get(&proposal.old_id) else {
+            continue;
+        };
+
+        let mut migrated = SkillData {
+            id: proposal.new_id.clone(),
+            ..Default::default()
+        };
+        for (field_type, text_type) in &source.text_type_map {
+            let value = old.get_field(field_type);
+            if !value.is_empty() {
+                migrated.insert_data(text_type.clone(), field_type.clone(), value.clone());
+            }
+        }
+        if !is_available_skill_data(&migrated) {
+            continue;
+        }
+
+        manager
+            .translation_skills
+            .insert(proposal.new_id.clone(), migrated);
+        if !manager.skills.contains_key(&proposal.old_id) {
+            manager.translation_skills.remove(&proposal.old_id);
+        }
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{self, skill_map, tip_skill, with_context};
+
+    // 上層物件都是ANcl
+    fn context<'a>(name: &'a str, hotkey: &'a str) -> [(ContextType, &'a str); 3] {
+        [
+            (ContextType::Name, name),
+            (ContextType::Parent, "ANcl"),
+            (ContextType::Hotkey, hotkey),
+        ]
+    }
+
+    #[test]
+    fn test_propose_and_apply_remaps() {
+        let mut manager = test_util::manager(
+            vec![
+                with_context(
+                    tip_skill("A0X3", &["|c00ffff80일도양단|r(|cffffcc00D|r)"]),
+                    &context("Sword Slash", "D"),
+                ),
+                with_context(
+                    tip_skill("A0X4", &["마력 전달과 흡수|cffffcc00(Q)|r "]),
+                    &context("Mana Drain", "Q"),
+                ),
+            ],
+            vec![tip_skill("A011", &["一刀兩斷(D)"])],
+        );
+        let legacy = ReferenceData {
+            skills: skill_map(vec![
+                with_context(
+                    tip_skill("A011", &["|c00ffff80일도양단|r(|cffffcc00D|r)"]),
+                    &context("Sword Slash", "D"),
+                ),
+                with_context(
+                    tip_skill("A038", &["마력 전달과 흡수|cffffcc00(Q)|r "]),
+                    &context("Mana Drain", "Q"),
+                ),
+            ]),
+            translation_skills: skill_map(vec![
+                tip_skill("A011", &["一刀兩斷(D)"]),
+                tip_skill("A038", &["魔力傳遞與吸收(Q)"]),
+            ]),
+        };
+
+        let proposals = propose_remaps(&manager, &legacy);
+        assert_eq!(proposals.len(), 2);
+        assert_eq!(proposals[0].old_id, "A011");
+        assert_eq!(proposals[0].new_id, "A0X3");
+        assert_eq!(proposals[1].old_id, "A038");
+        assert_eq!(proposals[1].new_id, "A0X4");
+        assert!(proposals.iter().all(|p| p.selected));
+
+        assert_eq!(apply_remaps(&mut manager, &legacy, &proposals), 2);
+        assert!(!manager.translation_skills.contains_key("A011"));
+        assert_eq!(manager.translation_skills["A0X3"].tip, vec!["一刀兩斷(D)"]);
+        assert_eq!(
+            manager.translation_skills["A0X4"].tip,
+            vec!["魔力傳遞與吸收(Q)"]
+        );
+    }
+
+    #[test]
+    fn test_score_pair_prefers_matching_context() {
+        let old = with_context(
+            tip_skill("A011", &["일도양단"]),
+            &context("Sword Slash", "D"),
+        );
+        let same = with_context(
+            tip_skill("A0X3", &["일도양단"]),
+            &context("Sword Slash", "D"),
+        );
+        let other = with_context(
+            tip_skill("A0X4", &["마력 흡수"]),
+            &context("Mana Drain", "Q"),
+        );
+        assert!(score_pair(&old, &same).score > 0.99);
+        assert!(score_pair(&old, &other).score < REMAP_THRESHOLD);
+    }
+}
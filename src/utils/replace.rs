@@ -0,0 +1,247 @@
+use crate::data::tooltip::{SegmentKey, SkillManager};
+use crate::utils::common::{FieldType, ObjectTable};
+use crate::utils::markup::{tokenize_markup, MarkupKind};
+use crate::utils::status::mark_edited;
+use regex::{NoExpand, Regex};
+
+#[derive(Debug, Clone)]
+pub struct ReplaceQuery {
+    pub find: String,
+    pub replace: String,
+    pub use_regex: bool,
+    // 空的清單代表不限制
+    pub field_types: Vec<FieldType>,
+    pub tables: Vec<ObjectTable>,
+    // 技能ID，可使用*與?萬用字元
+    pub id_pattern: String,
+    pub skip_color_codes: bool,
+}
+
+impl Default for ReplaceQuery {
+    fn default() -> Self {
+        Self {
+            find: String::new(),
+            replace: String::new(),
+            use_regex: false,
+            field_types: vec![],
+            tables: vec![],
+            id_pattern: String::new(),
+            skip_color_codes: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplaceMatch {
+    pub key: SegmentKey,
+    pub before: String,
+    pub after: String,
+    pub count: usize,
+    pub selected: bool,
+}
+
+fn id_regex(pattern: &str) -> Result<Option<Regex>, String> {
+    if pattern.trim().is_empty() {
+        return Ok(None);
+    }
+    let mut expression = String::from("^");
+    for c in pattern.trim().chars() {
+        match c {
+            '*' => expression.push_str(".*"),
+            '?' => expression.push('.'),
+            c => expression.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    expression.push('$');
+    Regex::new(&expression)
+        .map(Some)
+        .map_err(|e| format!("ID格式錯誤: {}", e))
+}
+
+fn find_regex(query: &ReplaceQuery) -> Result<Regex, String> {
+    if query.find.is_empty() {
+        return Err("請輸入要尋找的文字".to_string());
+    }
+    match query.use_regex {
+        true => Regex::new(&query.find).map_err(|e| format!("正規表示式錯誤: {}", e)),
+        false => Regex::new(&regex::escape(&query.find)).map_err(|e| e.to_string()),
+    }
+}
+
+// 回傳取代後的文字與取代次數
+fn replace_text(text: &str, pattern: &Regex, query: &ReplaceQuery) -> (String, usize) {
+    let replace = |segment: &str| -> (String, usize) {
+        let count = pattern.find_iter(segment).count();
+        let replaced = match query.use_regex {
+            true => pattern.replace_all(segment, query.replace.as_str()),
+            false => pattern.replace_all(segment, NoExpand(&query.replace)),
+        };
+        (replaced.into_owned(), count)
+    };
+    if !query.skip_color_codes {
+        return replace(text);
+    }
+
+    // 只取代色碼之外的文字
+    let mut result = String::new();
+    let mut total = 0;
+    for span in tokenize_markup(text) {
+        let segment = &text[span.range];
+        if span.kind == MarkupKind::Text {
+            let (replaced, count) = replace(segment);
+            result.push_str(&replaced);
+            total += count;
+        } else {
+            result.push_str(segment);
+        }
+    }
+    (result, total)
+}
+
+// 列出整個專案中會被取代的翻譯段落
+pub fn find_matches(
+    manager: &SkillManager,
+    query: &ReplaceQuery,
+) -> Result<Vec<ReplaceMatch>, String> {
+    let pattern = find_regex(query)?;
+    let id_pattern = id_regex(&query.id_pattern)?;
+
+    let mut matches = vec![];
+    for key in manager.segment_keys() {
+        if !query.field_types.is_empty() && !query.field_types.contains(&key.field_type) {
+            continue;
+        }
+        if !query.tables.is_empty() && !query.tables.contains(&ObjectTable::from_id(&key.id)) {
+            continue;
+        }
+        if id_pattern.as_ref().is_some_and(|p| !p.is_match(&key.id)) {
+            continue;
+        }
+        let Some(before) = manager.get_translation(&key) else {
+            continue;
+        };
+        let (after, count) = replace_text(before, &pattern, query);
+        if count > 0 && &after != before {
+            matches.push(ReplaceMatch {
+                before: before.clone(),
+                after,
+                count,
+                key,
+                selected: true,
+            });
+        }
+    }
+    Ok(matches)
+}
+
+// 套用選取的取代結果，回傳實際取代的段落數
+pub fn apply_matches(manager: &mut SkillManager, matches: &[ReplaceMatch]) -> usize {
+    let mut count = 0;
+    for found in matches.iter().filter(|m| m.selected) {
+        // 預覽之後翻譯又被修改過就略過
+        let Some(translation) = manager.get_translation_mut(&found.key) else {
+            continue;
+        };
+        if *translation != found.before {
+            continue;
+        }
+        *translation = found.after.clone();
+        mark_edited(manager, &found.key);
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{self, tip_skill};
+    use crate::data::tooltip::SegmentStatus;
+
+    fn manager() -> SkillManager {
+        test_util::manager(
+            ["A000", "A001", "I000"]
+                .into_iter()
+                .map(|id| tip_skill(id, &["마나", "마나"]))
+                .collect(),
+            vec![
+                tip_skill("A000", &["|cffcc00cc魔力|r 魔力", "魔力 100"]),
+                tip_skill("I000", &["魔力", "法力"]),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_find_matches() {
+        let manager = manager();
+        let query = ReplaceQuery {
+            find: "魔力".to_string(),
+            replace: "法力".to_string(),
+            ..Default::default()
+        };
+        let matches = find_matches(&manager, &query).unwrap();
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[0].after, "|cffcc00cc法力|r 法力");
+        assert_eq!(matches[0].count, 2);
+
+        let query = ReplaceQuery {
+            find: "cc".to_string(),
+            replace: "dd".to_string(),
+            ..Default::default()
+        };
+        assert!(find_matches(&manager, &query).unwrap().is_empty());
+
+        let query = ReplaceQuery {
+            find: r"魔力 (\d+)".to_string(),
+            replace: "法力$1點".to_string(),
+            use_regex: true,
+            tables: vec![ObjectTable::Ability],
+            ..Default::default()
+        };
+        let matches = find_matches(&manager, &query).unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].after, "法力100點");
+
+        let query = ReplaceQuery {
+            find: "魔力".to_string(),
+            replace: "$1".to_string(),
+            id_pattern: "I*".to_string(),
+            ..Default::default()
+        };
+        let matches = find_matches(&manager, &query).unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].after, "$1");
+
+        let query = ReplaceQuery {
+            find: "(".to_string(),
+            use_regex: true,
+            ..Default::default()
+        };
+        assert!(find_matches(&manager, &query).is_err());
+    }
+
+    #[test]
+    fn test_apply_matches() {
+        let mut manager = manager();
+        let query = ReplaceQuery {
+            find: "魔力".to_string(),
+            replace: "法力".to_string(),
+            ..Default::default()
+        };
+        let mut matches = find_matches(&manager, &query).unwrap();
+        matches[2].selected = false;
+        *manager.get_translation_mut(&matches[1].key).unwrap() = "魔力 200".to_string();
+        manager
+            .statuses
+            .insert(matches[0].key.clone(), SegmentStatus::Approved);
+
+        assert_eq!(apply_matches(&mut manager, &matches), 1);
+        // 取代過的翻譯需要重新審校
+        assert_eq!(manager.status(&matches[0].key), SegmentStatus::Translated);
+        assert_eq!(
+            manager.translation_skills["A000"].tip,
+            vec!["|cffcc00cc法力|r 法力", "魔力 200"]
+        );
+        assert_eq!(manager.translation_skills["I000"].tip, vec!["魔力", "法力"]);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::data::tooltip::{SegmentKey, SkillData, SkillManager};
+use crate::utils::common::FieldType;
+
+// 以原文覆蓋單一等級的翻譯
+pub fn reset_level(manager: &mut SkillManager, key: &SegmentKey) -> bool {
+    let Some(source) = manager.get_source(key).cloned() else {
+        return false;
+    };
+    manager.review_flags.remove(key);
+    manager.set_translation(key, source)
+}
+
+// 只補上翻譯中缺少的欄位，已有的欄位不受影響
+pub fn fill_missing_fields(manager: &mut SkillManager, id: &str) -> Vec<FieldType> {
+    let Some(source) = manager.skills.get(id) else {
+        return vec![];
+    };
+    let translated = manager
+        .translation_skills
+        .entry(id.to_string())
+        .or_insert_with(|| SkillData {
+            id: id.to_string(),
+            context: source.context.clone(),
+            ..Default::default()
+        });
+
+    let mut filled = vec![];
+    for field_type in FieldType::ALL.iter() {
+        let value = source.get_field(field_type);
+        if value.is_empty() || !translated.get_field(field_type).is_empty() {
+            continue;
+        }
+        *translated.get_field_mut(field_type) = value.clone();
+        if let Some(text_type) = source.text_type_map.get(field_type) {
+            translated
+                .text_type_map
+                .insert(field_type.clone(), text_type.clone());
+        }
+        filled.push(field_type.clone());
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{self, skill, tip_skill};
+    use crate::data::tooltip::ReviewFlag;
+    use crate::utils::common::TextType;
+
+    fn manager() -> SkillManager {
+        let source = skill(
+            "A000",
+            TextType::SingleLineArray,
+            &[
+                (FieldType::Tip, &["레벨 1", "레벨 2", "레벨 3"]),
+                (FieldType::Ubertip, &["데미지"]),
+            ],
+        );
+        test_util::manager(vec![source], vec![tip_skill("A000", &["等級 1", "等級 2"])])
+    }
+
+    #[test]
+    fn test_reset_level() {
+        let mut manager = manager();
+        let key = SegmentKey::new("A000", FieldType::Tip, 1);
+        manager
+            .review_flags
+            .insert(key.clone(), ReviewFlag::Generated);
+        assert!(reset_level(&mut manager, &key));
+        assert_eq!(
+            manager.translation_skills["A000"].tip,
+            vec!["等級 1", "레벨 2", "레벨 3"]
+        );
+        assert!(manager.review_flags.is_empty());
+    }
+
+    #[test]
+    fn test_fill_missing_fields() {
+        let mut manager = manager();
+        assert_eq!(
+            fill_missing_fields(&mut manager, "A000"),
+            vec![FieldType::Ubertip]
+        );
+        assert_eq!(manager.translation_skills["A000"].ubertip, vec!["데미지"]);
+        assert_eq!(
+            manager.translation_skills["A000"].tip,
+            vec!["等級 1", "等級 2"]
+        );
+        assert!(fill_missing_fields(&mut manager, "A000").is_empty());
+    }
+}
@@ -0,0 +1,102 @@
+use crate::data::tooltip::{ReviewFlag, SegmentKey};
+use crate::utils::common::*;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+
+pub const REVIEW_FLAG_ID_REGEX: &str = r#"^\[(.+)\]$"#;
+pub const REVIEW_FLAG_REGEX: &str = r#"^([A-Za-z]+)#(\d+)\s*=\s*"(.*)"$"#;
+
+pub fn load_review_flags(path: &str) -> Result<BTreeMap<SegmentKey, ReviewFlag>, String> {
+    Ok(parse_review_flags(&read_lossy(path)?))
+}
+
+pub fn save_review_flags(
+    path: &str,
+    flags: &BTreeMap<SegmentKey, ReviewFlag>,
+) -> Result<(), String> {
+    fs::write(path, output_review_flags(flags)).map_err(|e| e.to_string())
+}
+
+pub fn parse_review_flags(content: &str) -> BTreeMap<SegmentKey, ReviewFlag> {
+    let id_pattern = Regex::new(REVIEW_FLAG_ID_REGEX).unwrap();
+    let pattern = Regex::new(REVIEW_FLAG_REGEX).unwrap();
+
+    let mut flags = BTreeMap::new();
+    let mut current_id = None;
+    for line in content.lines() {
+        if let Some(caps) = id_pattern.captures(line) {
+            current_id = Some(caps[1].to_string());
+            continue;
+        }
+
+        let (Some(id), Some(caps)) = (&current_id, pattern.captures(line)) else {
+            continue;
+        };
+        let Some(field_type) = FieldType::from_str(&caps[1]) else {
+            continue;
+        };
+        let Ok(level) = caps[2].parse::<usize>() else {
+            continue;
+        };
+        let Some(flag) = ReviewFlag::from_id(&caps[3]) else {
+            continue;
+        };
+        flags.insert(
+            SegmentKey::new(id, field_type, level.saturating_sub(1)),
+            flag,
+        );
+    }
+
+    flags
+}
+
+pub fn output_review_flags(flags: &BTreeMap<SegmentKey, ReviewFlag>) -> String {
+    let mut output = String::new();
+    let mut current_id: Option<&str> = None;
+    for (key, flag) in flags {
+        if current_id != Some(key.id.as_str()) {
+            if current_id.is_some() {
+                output.push(NEWLINE_SYMBOL);
+            }
+            output.push_line(&format!("[{}]", key.id));
+            current_id = Some(key.id.as_str());
+        }
+        output.push_line(&format!(
+            "{}#{} = \"{}\"",
+            key.field_type.to_str(),
+            key.level + 1,
+            flag.to_id()
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_output_review_flags() {
+        let flags = BTreeMap::from([
+            (
+                SegmentKey::new("A000", FieldType::Tip, 1),
+                ReviewFlag::Generated,
+            ),
+            (
+                SegmentKey::new("A000", FieldType::Ubertip, 0),
+                ReviewFlag::AutoPropagated,
+            ),
+            (
+                SegmentKey::new("A001", FieldType::Tip, 0),
+                ReviewFlag::Queued,
+            ),
+        ]);
+        let content = output_review_flags(&flags);
+        assert_eq!(
+            content,
+            "[A000]\nTip#2 = \"generated\"\nUbertip#1 = \"propagated\"\n\n[A001]\nTip#1 = \"queued\"\n"
+        );
+        assert_eq!(parse_review_flags(&content), flags);
+    }
+}
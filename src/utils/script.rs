@@ -0,0 +1,103 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Script {
+    Hangul,
+    Han,
+    Kana,
+    Latin,
+    Cyrillic,
+}
+
+impl Script {
+    pub const ALL: [Script; 5] = [
+        Script::Hangul,
+        Script::Han,
+        Script::Kana,
+        Script::Latin,
+        Script::Cyrillic,
+    ];
+
+    const HANGUL: &'static str = "Hangul";
+    const HAN: &'static str = "Han";
+    const KANA: &'static str = "Kana";
+    const LATIN: &'static str = "Latin";
+    const CYRILLIC: &'static str = "Cyrillic";
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Script> {
+        match s {
+            Self::HANGUL => Some(Script::Hangul),
+            Self::HAN => Some(Script::Han),
+            Self::KANA => Some(Script::Kana),
+            Self::LATIN => Some(Script::Latin),
+            Self::CYRILLIC => Some(Script::Cyrillic),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Script::Hangul => Self::HANGUL,
+            Script::Han => Self::HAN,
+            Script::Kana => Self::KANA,
+            Script::Latin => Self::LATIN,
+            Script::Cyrillic => Self::CYRILLIC,
+        }
+    }
+
+    pub fn to_label(&self) -> &'static str {
+        match self {
+            Script::Hangul => "韓文",
+            Script::Han => "漢字",
+            Script::Kana => "日文假名",
+            Script::Latin => "拉丁字母",
+            Script::Cyrillic => "西里爾字母",
+        }
+    }
+}
+
+// 數字、符號與空白不屬於任何文字
+pub fn script_of(c: char) -> Option<Script> {
+    match c as u32 {
+        0xAC00..=0xD7A3 | 0x1100..=0x11FF | 0x3130..=0x318F | 0xA960..=0xA97F | 0xD7B0..=0xD7FF => {
+            Some(Script::Hangul)
+        }
+        0x4E00..=0x9FFF | 0x3400..=0x4DBF | 0xF900..=0xFAFF | 0x20000..=0x2FFFF => {
+            Some(Script::Han)
+        }
+        0x3040..=0x309F | 0x30A1..=0x30FA | 0x30FC..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => {
+            Some(Script::Kana)
+        }
+        0x0400..=0x04FF => Some(Script::Cyrillic),
+        0xFF21..=0xFF3A | 0xFF41..=0xFF5A => Some(Script::Latin),
+        _ if c.is_ascii_alphabetic() => Some(Script::Latin),
+        0x00C0..=0x024F if c.is_alphabetic() => Some(Script::Latin),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_of() {
+        let scripts: Vec<Option<Script>> = "레가あア漢aéЖ1・ー ".chars().map(script_of).collect();
+        assert_eq!(
+            scripts,
+            vec![
+                Some(Script::Hangul),
+                Some(Script::Hangul),
+                Some(Script::Kana),
+                Some(Script::Kana),
+                Some(Script::Han),
+                Some(Script::Latin),
+                Some(Script::Latin),
+                Some(Script::Cyrillic),
+                None,
+                None,
+                Some(Script::Kana),
+                None,
+            ]
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+// 以字元雙連詞(bigram)計算Dice係數，回傳0.0~1.0
+pub fn similarity(a: &str, b: &str) -> f32 {
+    if a == b {
+        return 1.0;
+    }
+
+    let a_bigrams = bigrams(a);
+    let b_bigrams = bigrams(b);
+    let total: usize = a_bigrams.values().sum::<usize>() + b_bigrams.values().sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let shared: usize = a_bigrams
+        .iter()
+        .map(|(k, count)| b_bigrams.get(k).map_or(0, |other| (*count).min(*other)))
+        .sum();
+    (2 * shared) as f32 / total as f32
+}
+
+fn bigrams(text: &str) -> HashMap<(char, char), usize> {
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut result = HashMap::new();
+    for pair in chars.windows(2) {
+        *result.entry((pair[0], pair[1])).or_insert(0) += 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(similarity("일도양단", "일도양단"), 1.0);
+        assert_eq!(similarity("abcd", "wxyz"), 0.0);
+        assert_eq!(similarity("", "abc"), 0.0);
+
+        let close = similarity(
+            "정면 1600범위에 1000의 데미지",
+            "정면 1600범위에 1350의 데미지",
+        );
+        let far = similarity("정면 1600범위에 1000의 데미지", "마력을 흡수합니다");
+        assert!(close > 0.7);
+        assert!(far < 0.2);
+    }
+}
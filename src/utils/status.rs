@@ -0,0 +1,338 @@
+use crate::data::tooltip::{ReviewFlag, SegmentKey, SegmentStatus, SkillData, SkillManager};
+use crate::utils::common::*;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+
+pub const STATUS_ID_REGEX: &str = r#"^\[(.+)\]$"#;
+pub const STATUS_REGEX: &str = r#"^([A-Za-z]+)#(\d+)\s*=\s*"(.*)"$"#;
+
+pub fn load_statuses(path: &str) -> Result<BTreeMap<SegmentKey, SegmentStatus>, String> {
+    let content = read_lossy(path)?;
+    Ok(parse_statuses(&content))
+}
+
+pub fn save_statuses(
+    path: &str,
+    statuses: &BTreeMap<SegmentKey, SegmentStatus>,
+) -> Result<(), String> {
+    fs::write(path, output_statuses(statuses)).map_err(|e| e.to_string())
+}
+
+pub fn parse_statuses(content: &str) -> BTreeMap<SegmentKey, SegmentStatus> {
+    let id_pattern = Regex::new(STATUS_ID_REGEX).unwrap();
+    let pattern = Regex::new(STATUS_REGEX).unwrap();
+
+    let mut statuses = BTreeMap::new();
+    let mut current_id = None;
+    for line in content.lines() {
+        if let Some(caps) = id_pattern.captures(line) {
+            current_id = Some(caps[1].to_string());
+            continue;
+        }
+
+        let (Some(id), Some(caps)) = (&current_id, pattern.captures(line)) else {
+            continue;
+        };
+        let Some(field_type) = FieldType::from_str(&caps[1]) else {
+            continue;
+        };
+        let Ok(level) = caps[2].parse::<usize>() else {
+            continue;
+        };
+        let Some(status) = SegmentStatus::from_id(&caps[3]) else {
+            continue;
+        };
+        statuses.insert(
+            SegmentKey::new(id, field_type, level.saturating_sub(1)),
+            status,
+        );
+    }
+
+    statuses
+}
+
+pub fn output_statuses(statuses: &BTreeMap<SegmentKey, SegmentStatus>) -> String {
+    let mut output = String::new();
+    let mut current_id: Option<&str> = None;
+    for (key, status) in statuses {
+        if current_id != Some(key.id.as_str()) {
+            if current_id.is_some() {
+                output.push(NEWLINE_SYMBOL);
+            }
+            output.push_line(&format!("[{}]", key.id));
+            current_id = Some(key.id.as_str());
+        }
+        output.push_line(&format!(
+            "{}#{} = \"{}\"",
+            key.field_type.to_str(),
+            key.level + 1,
+            status.to_id()
+        ));
+    }
+    output
+}
+
+// 空白的翻譯視為未翻譯，與原文相同的翻譯(專有名詞、純數字等)要由使用者設定狀態後才算已翻譯
+pub fn is_translated_text(source: &str, translation: &str, status: Option<&SegmentStatus>) -> bool {
+    if translation.is_empty() {
+        return false;
+    }
+    source != translation || status.is_some_and(|status| status != &SegmentStatus::Untranslated)
+}
+
+// 未翻譯的段落一律視為未翻譯，自動產生且尚未確認的翻譯視為機器填入
+pub fn segment_status(
+    translated: bool,
+    status: Option<&SegmentStatus>,
+    flag: Option<&ReviewFlag>,
+) -> SegmentStatus {
+    if !translated {
+        return SegmentStatus::Untranslated;
+    }
+    if let Some(status) = status {
+        return status.clone();
+    }
+    match flag {
+        Some(ReviewFlag::AutoPropagated | ReviewFlag::Generated) => SegmentStatus::MachineFilled,
+        _ => SegmentStatus::Translated,
+    }
+}
+
+// 自動填入的翻譯同時寫入標記與對應的狀態，覆蓋原本的狀態，標記檔遺失時仍保留機器填入的狀態
+pub fn set_review_flag(manager: &mut SkillManager, key: &SegmentKey, flag: ReviewFlag) {
+    match flag {
+        ReviewFlag::AutoPropagated | ReviewFlag::Generated => {
+            manager
+                .statuses
+                .insert(key.clone(), SegmentStatus::MachineFilled);
+        }
+        ReviewFlag::Queued => {
+            manager.statuses.remove(key);
+        }
+    }
+    manager.review_flags.insert(key.clone(), flag);
+}
+
+// 確認自動填入的翻譯，機器填入的狀態改回依翻譯內容判斷
+pub fn confirm_review_flag(
+    review_flags: &mut BTreeMap<SegmentKey, ReviewFlag>,
+    statuses: &mut BTreeMap<SegmentKey, SegmentStatus>,
+    key: &SegmentKey,
+) {
+    review_flags.remove(key);
+    if statuses.get(key) == Some(&SegmentStatus::MachineFilled) {
+        statuses.remove(key);
+    }
+}
+
+// 使用者修改翻譯後，草稿之前的狀態改為草稿，已審校或核准的翻譯需要重新審校
+pub fn mark_edited(manager: &mut SkillManager, key: &SegmentKey) {
+    let status = match manager.status(key) {
+        SegmentStatus::Untranslated => return,
+        SegmentStatus::MachineFilled => SegmentStatus::Draft,
+        SegmentStatus::Reviewed | SegmentStatus::Approved => SegmentStatus::Translated,
+        _ => return,
+    };
+    manager.statuses.insert(key.clone(), status);
+}
+
+// 整批取代翻譯(合併、還原)時，內容改變的段落與使用者修改一樣調整狀態
+pub fn replace_translations(manager: &mut SkillManager, skills: BTreeMap<String, SkillData>) {
+    let previous = std::mem::replace(&mut manager.translation_skills, skills);
+    for key in manager.segment_keys() {
+        let old = previous
+            .get(&key.id)
+            .and_then(|data| data.get_field(&key.field_type).get(key.level));
+        if old != manager.get_translation(&key) {
+            mark_edited(manager, &key);
+        }
+    }
+}
+
+pub fn ids_with_status(manager: &SkillManager, status: &SegmentStatus) -> Vec<String> {
+    let mut ids: Vec<String> = manager
+        .segment_keys()
+        .into_iter()
+        .filter(|key| &manager.status(key) == status)
+        .map(|key| key.id)
+        .collect();
+    ids.dedup();
+    ids
+}
+
+// 匯出用的翻譯，狀態低於下限的段落改用原文
+pub fn filter_by_status(
+    manager: &SkillManager,
+    min_status: &SegmentStatus,
+) -> BTreeMap<String, SkillData> {
+    let mut skills = manager.translation_skills.clone();
+    for key in manager.segment_keys() {
+        if &manager.status(&key) >= min_status {
+            continue;
+        }
+        let (Some(source), Some(data)) = (manager.get_source(&key), skills.get_mut(&key.id)) else {
+            continue;
+        };
+        if let Some(value) = data.get_field_mut(&key.field_type).get_mut(key.level) {
+            *value = source.clone();
+        }
+    }
+    skills
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{self, tip_skill};
+
+    fn manager() -> SkillManager {
+        test_util::manager(
+            vec![tip_skill("A000", &["레벨 1", "레벨 2", "레벨 3"])],
+            vec![tip_skill("A000", &["等級 1", "等級 2", "레벨 3"])],
+        )
+    }
+
+    #[test]
+    fn test_parse_and_output_statuses() {
+        let statuses = BTreeMap::from([
+            (
+                SegmentKey::new("A000", FieldType::Tip, 0),
+                SegmentStatus::Approved,
+            ),
+            (
+                SegmentKey::new("A000", FieldType::Ubertip, 1),
+                SegmentStatus::Draft,
+            ),
+            (
+                SegmentKey::new("A001", FieldType::Tip, 0),
+                SegmentStatus::Reviewed,
+            ),
+        ]);
+        let content = output_statuses(&statuses);
+        assert_eq!(
+            content,
+            "[A000]\nTip#1 = \"approved\"\nUbertip#2 = \"draft\"\n\n[A001]\nTip#1 = \"reviewed\"\n"
+        );
+        assert_eq!(parse_statuses(&content), statuses);
+    }
+
+    #[test]
+    fn test_status_workflow() {
+        let mut manager = manager();
+        let first = SegmentKey::new("A000", FieldType::Tip, 0);
+        let second = SegmentKey::new("A000", FieldType::Tip, 1);
+        let third = SegmentKey::new("A000", FieldType::Tip, 2);
+        manager
+            .review_flags
+            .insert(second.clone(), ReviewFlag::AutoPropagated);
+
+        assert_eq!(manager.status(&first), SegmentStatus::Translated);
+        assert_eq!(manager.status(&second), SegmentStatus::MachineFilled);
+        assert_eq!(manager.status(&third), SegmentStatus::Untranslated);
+
+        mark_edited(&mut manager, &second);
+        assert_eq!(manager.status(&second), SegmentStatus::Draft);
+        manager
+            .statuses
+            .insert(first.clone(), SegmentStatus::Approved);
+        assert_eq!(
+            ids_with_status(&manager, &SegmentStatus::Approved),
+            vec!["A000".to_string()]
+        );
+        assert!(ids_with_status(&manager, &SegmentStatus::Reviewed).is_empty());
+
+        let exported = filter_by_status(&manager, &SegmentStatus::Translated);
+        assert_eq!(
+            exported["A000"].tip,
+            vec![
+                "等級 1".to_string(),
+                "레벨 2".to_string(),
+                "레벨 3".to_string()
+            ]
+        );
+
+        mark_edited(&mut manager, &first);
+        assert_eq!(manager.status(&first), SegmentStatus::Translated);
+    }
+
+    #[test]
+    fn test_set_and_confirm_review_flag() {
+        let mut manager = manager();
+        let key = SegmentKey::new("A000", FieldType::Tip, 0);
+        manager
+            .statuses
+            .insert(key.clone(), SegmentStatus::Approved);
+
+        set_review_flag(&mut manager, &key, ReviewFlag::AutoPropagated);
+        assert_eq!(manager.status(&key), SegmentStatus::MachineFilled);
+        // 只讀回狀態檔時仍是機器填入
+        let statuses = parse_statuses(&output_statuses(&manager.statuses));
+        assert_eq!(
+            segment_status(true, statuses.get(&key), None),
+            SegmentStatus::MachineFilled
+        );
+
+        confirm_review_flag(&mut manager.review_flags, &mut manager.statuses, &key);
+        assert!(manager.review_flags.is_empty());
+        assert_eq!(manager.status(&key), SegmentStatus::Translated);
+
+        set_review_flag(&mut manager, &key, ReviewFlag::Queued);
+        assert!(!manager.statuses.contains_key(&key));
+        assert_eq!(manager.review_flags.get(&key), Some(&ReviewFlag::Queued));
+    }
+
+    #[test]
+    fn test_translation_same_as_source() {
+        let mut manager = manager();
+        let third = SegmentKey::new("A000", FieldType::Tip, 2);
+        assert!(!manager.is_translated(&third));
+
+        // 專有名詞等保留原文的翻譯由使用者設定狀態
+        manager
+            .statuses
+            .insert(third.clone(), SegmentStatus::Approved);
+        assert!(manager.is_translated(&third));
+        assert_eq!(manager.status(&third), SegmentStatus::Approved);
+        assert_eq!(
+            ids_with_status(&manager, &SegmentStatus::Approved),
+            vec!["A000".to_string()]
+        );
+
+        manager
+            .statuses
+            .insert(third.clone(), SegmentStatus::Untranslated);
+        assert!(!manager.is_translated(&third));
+        assert!(!is_translated_text(
+            "마나",
+            "",
+            Some(&SegmentStatus::Approved)
+        ));
+    }
+
+    #[test]
+    fn test_bulk_changes_update_statuses() {
+        let mut manager = manager();
+        let first = SegmentKey::new("A000", FieldType::Tip, 0);
+        let second = SegmentKey::new("A000", FieldType::Tip, 1);
+        manager
+            .statuses
+            .insert(first.clone(), SegmentStatus::Approved);
+        manager
+            .statuses
+            .insert(second.clone(), SegmentStatus::Reviewed);
+
+        // 合併或還原後內容改變的段落退回已翻譯，沒有改變的保留原本的狀態
+        let mut skills = manager.translation_skills.clone();
+        skills.get_mut("A000").unwrap().tip[1] = "第2級".to_string();
+        replace_translations(&mut manager, skills);
+        assert_eq!(manager.status(&first), SegmentStatus::Approved);
+        assert_eq!(manager.status(&second), SegmentStatus::Translated);
+
+        // 等級數減少時移除多出等級的狀態
+        manager.skills.get_mut("A000").unwrap().tip.truncate(1);
+        assert!(manager.sync_levels("A000", &FieldType::Tip));
+        assert!(!manager.statuses.contains_key(&second));
+        assert!(manager.statuses.contains_key(&first));
+    }
+}
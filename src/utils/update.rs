@@ -0,0 +1,364 @@
+use crate::data::tooltip::{ReviewFlag, SkillData, SkillManager};
+use crate::utils::archive::merge_archive;
+use crate::utils::common::{FieldType, SOURCE_FILE_NAME};
+use crate::utils::fingerprint::SourceFingerprints;
+use crate::utils::orphan::take_translation;
+use crate::utils::status::{mark_edited, set_review_flag};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+
+pub const UPDATE_SOURCE_FILE_NAME: &str = "source_update.ini";
+pub const PREVIOUS_SOURCE_FILE_NAME: &str = "source_previous.ini";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl ChangeKind {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "新增",
+            ChangeKind::Removed => "刪除",
+            ChangeKind::Changed => "變更",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceChange {
+    pub id: String,
+    // None代表整個物件
+    pub field_type: Option<FieldType>,
+    pub kind: ChangeKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdateOptions {
+    // 原文變更的已翻譯段落標記為過時，否則視為已依新原文更新
+    pub mark_stale: bool,
+    // 刪除的物件與欄位移到封存檔，否則保留在翻譯中
+    pub archive_removed: bool,
+    // 新增或變更的未翻譯段落以原文填入並加上待翻譯標記
+    pub queue_added: bool,
+}
+
+impl Default for UpdateOptions {
+    fn default() -> Self {
+        Self {
+            mark_stale: true,
+            archive_removed: true,
+            queue_added: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UpdateSummary {
+    pub unchanged: usize,
+    pub stale: usize,
+    pub archived: BTreeMap<String, SkillData>,
+    // 需要翻譯的段落數與原文字數
+    pub pending: usize,
+    pub pending_chars: usize,
+}
+
+pub fn compare_sources(
+    old: &BTreeMap<String, SkillData>,
+    new: &BTreeMap<String, SkillData>,
+) -> Vec<SourceChange> {
+    let ids: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    let mut changes = vec![];
+    for id in ids {
+        let (old_data, new_data) = match (old.get(id), new.get(id)) {
+            (Some(old_data), Some(new_data)) => (old_data, new_data),
+            (old_data, _) => {
+                let kind = match old_data {
+                    Some(_) => ChangeKind::Removed,
+                    None => ChangeKind::Added,
+                };
+                changes.push(SourceChange {
+                    id: id.clone(),
+                    field_type: None,
+                    kind,
+                });
+                continue;
+            }
+        };
+
+        for field_type in FieldType::ALL.iter() {
+            let old_values = old_data.get_field(field_type);
+            let new_values = new_data.get_field(field_type);
+            let kind = match (old_values.is_empty(), new_values.is_empty()) {
+                (true, false) => ChangeKind::Added,
+                (false, true) => ChangeKind::Removed,
+                _ if old_values != new_values => ChangeKind::Changed,
+                _ => continue,
+            };
+            changes.push(SourceChange {
+                id: id.clone(),
+                field_type: Some(field_type.clone()),
+                kind,
+            });
+        }
+    }
+    changes
+}
+
+// 以新的原文取代目前的原文並移轉翻譯，回傳需要處理的工作量
+pub fn apply_update(
+    manager: &mut SkillManager,
+    fingerprints: &mut SourceFingerprints,
+    new_skills: BTreeMap<String, SkillData>,
+    options: &UpdateOptions,
+) -> UpdateSummary {
+    let changes = compare_sources(&manager.skills, &new_skills);
+    let old_skills = std::mem::replace(&mut manager.skills, new_skills);
+    let mut summary = UpdateSummary::default();
+
+    for change in changes.iter().filter(|c| c.kind == ChangeKind::Removed) {
+        if options.archive_removed {
+            archive_removed(manager, change, &mut summary.archived);
+        }
+    }
+
+    for key in manager.segment_keys() {
+        let source = manager.get_source(&key).cloned().unwrap_or_default();
+        let old_source = old_skills
+            .get(&key.id)
+            .and_then(|data| data.get_field(&key.field_type).get(key.level));
+        let translated = manager.is_translated(&key);
+
+        // 原文沒有變更的未翻譯段落不算在這次更新的工作量中
+        if old_source == Some(&source) {
+            if translated {
+                summary.unchanged += 1;
+            }
+            continue;
+        } else if translated {
+            match (options.mark_stale, old_source) {
+                (true, Some(old_source)) => {
+                    if !fingerprints.entries.contains_key(&key) {
+                        fingerprints.record(&key, old_source);
+                    }
+                    // 原文變更後已審校或核准的翻譯需要重新審校
+                    mark_edited(manager, &key);
+                    summary.stale += 1;
+                }
+                _ => fingerprints.record(&key, &source),
+            }
+            continue;
+        }
+
+        summary.pending += 1;
+        summary.pending_chars += source.chars().count();
+        if options.queue_added {
+            manager.set_translation(&key, source);
+            set_review_flag(manager, &key, ReviewFlag::Queued);
+        }
+    }
+
+    summary
+}
+
+fn archive_removed(
+    manager: &mut SkillManager,
+    change: &SourceChange,
+    archived: &mut BTreeMap<String, SkillData>,
+) {
+    let Some(data) = take_translation(manager, &change.id, change.field_type.as_ref()) else {
+        return;
+    };
+    // 同一個物件可能有多個欄位被刪除，合併到同一筆封存資料
+    merge_archive(archived, BTreeMap::from([(change.id.clone(), data)]));
+}
+
+// 保留舊的source.ini作為下次比對的依據，再以新的原文取代
+pub fn replace_source_file(new_path: &str) -> Result<(), String> {
+    fs::copy(SOURCE_FILE_NAME, PREVIOUS_SOURCE_FILE_NAME)
+        .map_err(|e| format!("{}: {}", SOURCE_FILE_NAME, e))?;
+    fs::copy(new_path, SOURCE_FILE_NAME).map_err(|e| format!("{}: {}", new_path, e))?;
+    Ok(())
+}
+
+// 取代source.ini之後的步驟失敗時，以保留的舊原文還原
+pub fn restore_source_file() -> Result<(), String> {
+    fs::copy(PREVIOUS_SOURCE_FILE_NAME, SOURCE_FILE_NAME)
+        .map_err(|e| format!("{}: {}", PREVIOUS_SOURCE_FILE_NAME, e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::test_util::{skill, skill_map, tip_skill};
+    use crate::data::tooltip::{SegmentKey, SegmentStatus};
+    use crate::utils::common::TextType;
+
+    fn sources(tip: &[&str], ubertip: &[&str]) -> BTreeMap<String, SkillData> {
+        skill_map(vec![
+            skill(
+                "A000",
+                TextType::SingleLineArray,
+                &[(FieldType::Tip, tip), (FieldType::Ubertip, ubertip)],
+            ),
+            tip_skill("A001", &["마나"]),
+        ])
+    }
+
+    #[test]
+    fn test_compare_sources() {
+        let old = sources(&["레벨 1", "레벨 2"], &["설명"]);
+        let mut new = sources(&["레벨 1", "레벨 2 (강화)"], &[]);
+        new.remove("A001");
+        new.insert("A002".to_string(), tip_skill("A002", &["체력"]));
+
+        let changes = compare_sources(&old, &new);
+        let summary: Vec<(&str, Option<FieldType>, ChangeKind)> = changes
+            .iter()
+            .map(|c| (c.id.as_str(), c.field_type.clone(), c.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("A000", Some(FieldType::Tip), ChangeKind::Changed),
+                ("A000", Some(FieldType::Ubertip), ChangeKind::Removed),
+                ("A001", None, ChangeKind::Removed),
+                ("A002", None, ChangeKind::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_update() {
+        let mut manager = SkillManager {
+            skills: sources(&["레벨 1", "레벨 2"], &["설명"]),
+            ..Default::default()
+        };
+        manager.translation_skills = skill_map(vec![
+            skill(
+                "A000",
+                TextType::SingleLineArray,
+                &[
+                    (FieldType::Tip, &["等級 1", "等級 2"]),
+                    (FieldType::Ubertip, &["說明"]),
+                ],
+            ),
+            tip_skill("A001", &["魔力"]),
+        ]);
+
+        let mut new = sources(&["레벨 1", "레벨 2 (강화)", "레벨 3"], &[]);
+        new.remove("A001");
+        new.insert("A002".to_string(), tip_skill("A002", &["체력"]));
+
+        let stale = SegmentKey::new("A000", FieldType::Tip, 1);
+        let removed = SegmentKey::new("A001", FieldType::Tip, 0);
+        for key in [&stale, &removed] {
+            manager
+                .statuses
+                .insert(key.clone(), SegmentStatus::Approved);
+        }
+
+        let mut fingerprints = SourceFingerprints::default();
+        let summary = apply_update(
+            &mut manager,
+            &mut fingerprints,
+            new,
+            &UpdateOptions::default(),
+        );
+        assert_eq!(summary.unchanged, 1);
+        assert_eq!(summary.stale, 1);
+        // 原文變更的翻譯需要重新審校，封存的段落不再保留狀態
+        assert_eq!(manager.status(&stale), SegmentStatus::Translated);
+        assert!(!manager.statuses.contains_key(&removed));
+        assert_eq!(summary.pending, 2);
+        assert_eq!(summary.pending_chars, "레벨 3".chars().count() + 2);
+        assert_eq!(
+            summary.archived.keys().collect::<Vec<_>>(),
+            vec!["A000", "A001"]
+        );
+        assert_eq!(summary.archived["A000"].ubertip, vec!["說明".to_string()]);
+
+        assert_eq!(fingerprints.stale_keys(&manager), vec![stale]);
+        assert!(!manager.translation_skills.contains_key("A001"));
+        assert!(manager.translation_skills["A000"].ubertip.is_empty());
+
+        let queued = SegmentKey::new("A002", FieldType::Tip, 0);
+        assert_eq!(manager.get_translation(&queued), Some(&"체력".to_string()));
+        assert_eq!(manager.review_flags.get(&queued), Some(&ReviewFlag::Queued));
+        let new_level = SegmentKey::new("A000", FieldType::Tip, 2);
+        assert_eq!(
+            manager.review_flags.get(&new_level),
+            Some(&ReviewFlag::Queued)
+        );
+    }
+
+    #[test]
+    fn test_pending_only_counts_new_source() {
+        let mut manager = SkillManager {
+            skills: skill_map(vec![tip_skill("A000", &["레벨 1", "레벨 2"])]),
+            ..Default::default()
+        };
+        manager.translation_skills = skill_map(vec![tip_skill("A000", &["等級 1"])]);
+
+        // 第2級原本就沒有翻譯，原文也沒有變更，不算在這次更新的工作量中
+        let new = skill_map(vec![tip_skill("A000", &["레벨 1", "레벨 2", "레벨 3"])]);
+        let summary = apply_update(
+            &mut manager,
+            &mut SourceFingerprints::default(),
+            new,
+            &UpdateOptions::default(),
+        );
+        assert_eq!(summary.unchanged, 1);
+        assert_eq!(summary.pending, 1);
+        assert_eq!(summary.pending_chars, "레벨 3".chars().count());
+        assert!(!manager
+            .review_flags
+            .contains_key(&SegmentKey::new("A000", FieldType::Tip, 1)));
+        assert_eq!(
+            manager
+                .review_flags
+                .get(&SegmentKey::new("A000", FieldType::Tip, 2)),
+            Some(&ReviewFlag::Queued)
+        );
+    }
+
+    #[test]
+    fn test_archive_removed_fields_of_one_object() {
+        let mut manager = SkillManager {
+            skills: sources(&["레벨 1"], &["설명"]),
+            ..Default::default()
+        };
+        manager.translation_skills = skill_map(vec![skill(
+            "A000",
+            TextType::SingleLineArray,
+            &[
+                (FieldType::Tip, &["等級 1"]),
+                (FieldType::Ubertip, &["說明"]),
+            ],
+        )]);
+        let mut new = sources(&[], &[]);
+        new.insert(
+            "A000".to_string(),
+            skill(
+                "A000",
+                TextType::SingleLineArray,
+                &[(FieldType::Researchtip, &["연구"])],
+            ),
+        );
+
+        let summary = apply_update(
+            &mut manager,
+            &mut SourceFingerprints::default(),
+            new,
+            &UpdateOptions::default(),
+        );
+        let archived = &summary.archived["A000"];
+        assert_eq!(archived.tip, vec!["等級 1".to_string()]);
+        assert_eq!(archived.ubertip, vec!["說明".to_string()]);
+        let translated = &manager.translation_skills["A000"];
+        assert!(translated.tip.is_empty() && translated.ubertip.is_empty());
+    }
+}
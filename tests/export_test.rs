@@ -69,6 +69,7 @@ mod tests {
                     ),
                 ],
                 text_type_map,
+                ..Default::default()
             },
         );
 
@@ -1,5 +1,6 @@
 use std::fs;
 use std::path::PathBuf;
+use wts_translation_manager::utils::common::ContextType;
 use wts_translation_manager::utils::parser::parse_content;
 
 #[cfg(test)]
@@ -25,4 +26,23 @@ mod tests {
         assert!(!map.contains_key("A037"),"Should not found A037");
         assert!(map.contains_key("A038"),"Not found A038");
     }
+
+    #[test]
+    fn test_parser_context() {
+        let file_path = get_test_file_path("source.ini");
+        let content = fs::read_to_string(file_path).expect("Failed to read file");
+
+        let map = parse_content(&content);
+        let data = map.get("Az03").unwrap();
+        assert_eq!(data.context.get(&ContextType::Parent).unwrap(), "ANcl");
+        assert_eq!(data.context.get(&ContextType::Hotkey).unwrap(), "E");
+        assert_eq!(
+            data.context.get(&ContextType::Researchhotkey).unwrap(),
+            "E"
+        );
+        assert_eq!(
+            data.context.get(&ContextType::Name).unwrap(),
+            "(Az03) HassanKing - (E)"
+        );
+    }
 }